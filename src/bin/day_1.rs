@@ -1,55 +1,163 @@
-use std::fs::File;
-use std::io::prelude::*;
-use std::io::BufReader;
+extern crate my;
 
-type InputType = i32;
-const SHITHOLE_YEAR: InputType = 2020;
+use my::days::day1::{self, Aggregate, InputType, Prefer, SHITHOLE_YEAR};
+use my::runner;
+use std::env;
+use std::process;
 
-fn main() {
-    let mut file = File::open("inputs/day_1").expect("could not open input file");
-
-    let inputs = collect_inputs(&mut file);
-    do_magic(&inputs);
-}
+const USAGE: &str = "\
+usage: day_1 [<file|->] [--k <k>] [--target <n>] [--aggregate <product|sum|min|max>] [--all]
+       day_1 [<file|->] --subset [--target <n>] [--min <n>] [--max <n>] [--most]
 
-fn collect_inputs(file: &mut File) -> Vec<InputType> {
-    let mut reader = BufReader::new(file);
+Solves both parts, or with any option finds every combination of <k> entries (default 2) summing
+to <n> (default 2020), counts them and aggregates them: the product of the first combination,
+the sum of all their products, or the smallest or largest product. --all also lists them.
 
-    let mut inputs = Vec::<InputType>::new();
+--subset instead finds the subset of any size summing to <n> with the fewest, or --most, entries,
+optionally with at least and at most some number of entries.";
 
-    loop {
-        let mut buf = String::new();
-        match reader.read_line(&mut buf) {
-            Ok(x) if x != 0 => {
-                if let Ok(val) = buf.trim_end().parse::<InputType>() {
-                    inputs.push(val);
-                } else {
-                    panic!("failed to parse line {}", buf);
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut path = None;
+    let mut k = None;
+    let mut target = None;
+    let mut how = None;
+    let mut all = false;
+    let mut subset = false;
+    let mut min = None;
+    let mut max = None;
+    let mut prefer = Prefer::Fewest;
+    let mut args = args.iter().map(String::as_str);
+    while let Some(arg) = args.next() {
+        match arg {
+            "--k" => k = Some(number::<usize>(args.next())),
+            "--target" => target = Some(number::<InputType>(args.next())),
+            "--aggregate" => {
+                how = match args.next() {
+                    Some("product") => Some(Aggregate::Product),
+                    Some("sum") => Some(Aggregate::Sum),
+                    Some("min") => Some(Aggregate::MinProduct),
+                    Some("max") => Some(Aggregate::MaxProduct),
+                    _ => usage(),
                 }
             }
-
-            // Finished or failed reading
-            Ok(_) => break,
-            Err(_) => panic!("failed to read line"),
+            "--all" => all = true,
+            "--subset" => subset = true,
+            "--min" => min = Some(number::<usize>(args.next())),
+            "--max" => max = Some(number::<usize>(args.next())),
+            "--most" => prefer = Prefer::Most,
+            path_arg if path.is_none() && !path_arg.starts_with("--") => path = Some(path_arg),
+            _ => usage(),
         }
     }
-
-    return inputs;
+    if subset {
+        if k.is_some() || how.is_some() || all {
+            usage();
+        }
+        let target = target.unwrap_or(SHITHOLE_YEAR);
+        return find_subset(path, target, min.unwrap_or(1), max, prefer);
+    }
+    if min.is_some() || max.is_some() || prefer == Prefer::Most {
+        usage();
+    }
+    if k.is_none() && target.is_none() && how.is_none() && !all {
+        return runner::main_for_day(1);
+    }
+    let target = target.unwrap_or(SHITHOLE_YEAR);
+    return find_combinations(
+        path,
+        k.unwrap_or(2),
+        target,
+        how.unwrap_or(Aggregate::Product),
+        all,
+    );
 }
 
-fn do_magic(values: &Vec<InputType>) {
-    for i in 0..values.len() {
-        for j in i + 1..values.len() {
-            for k in j + 1..values.len() {
-                let candidate: Vec<InputType> = vec![values[i], values[j], values[k]];
-                let sum = candidate.iter().sum::<InputType>();
-                if sum == SHITHOLE_YEAR {
-                    // Found it
-                    println!("Result: {}", candidate.iter().product::<InputType>());
-                    return;
+/// Prints the summary of every combination of `k` entries summing to `target`, and with `all`
+/// the combinations.
+fn find_combinations(path: Option<&str>, k: usize, target: InputType, how: Aggregate, all: bool) {
+    let result = runner::open_input(1, path)
+        .and_then(day1::collect_inputs)
+        .and_then(|values| {
+            if all {
+                for indices in day1::combinations(&values, k, target) {
+                    let entries: Vec<String> =
+                        indices.iter().map(|&i| values[i].to_string()).collect();
+                    println!("{} = {}", entries.join(" + "), target);
                 }
             }
+            return day1::summarize(&values, k, target, how);
+        });
+    match result {
+        Ok(summary) => match summary.value {
+            Some(value) => println!(
+                "{} combinations of {} entries sum to {}, {} {}",
+                summary.count,
+                k,
+                target,
+                describe(how),
+                value
+            ),
+            None => fail(&my::Error::invariant(format!(
+                "could not find {} entries summing to {}",
+                k, target
+            ))),
+        },
+        Err(e) => fail(&e),
+    }
+}
+
+/// Prints the subset of `min` to `max` entries summing to `target`.
+fn find_subset(
+    path: Option<&str>,
+    target: InputType,
+    min: usize,
+    max: Option<usize>,
+    prefer: Prefer,
+) {
+    let result = runner::open_input(1, path)
+        .and_then(day1::collect_inputs)
+        .and_then(|values| {
+            let max = max.unwrap_or(values.len());
+            let indices = day1::subset_sum(&values, target, min, max, prefer)?;
+            return Ok(indices.iter().map(|&i| values[i]).collect::<Vec<_>>());
+        });
+    match result {
+        Ok(entries) => {
+            let terms: Vec<String> = entries.iter().map(InputType::to_string).collect();
+            println!(
+                "{} = {} ({} entries)",
+                terms.join(" + "),
+                target,
+                entries.len()
+            );
         }
+        Err(e) => fail(&e),
+    }
+}
+
+fn describe(how: Aggregate) -> &'static str {
+    match how {
+        Aggregate::Product => "product",
+        Aggregate::Sum => "sum of products",
+        Aggregate::MinProduct => "smallest product",
+        Aggregate::MaxProduct => "largest product",
     }
-    panic!("could not find a {} summing pair", SHITHOLE_YEAR);
+}
+
+fn fail(e: &my::Error) -> ! {
+    eprintln!("Day 1 failed: {}", e);
+    process::exit(e.kind().exit_code());
+}
+
+fn number<T: std::str::FromStr>(arg: Option<&str>) -> T {
+    match arg.map(str::parse) {
+        Some(Ok(number)) => number,
+        _ => usage(),
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
 }
@@ -1,67 +1,70 @@
 extern crate my;
 
-use my::input::InputReader;
+use my::days::day2::{self, Policy, Unit};
+use my::runner;
+use std::env;
+use std::process;
 
-fn main() {
-    let reader = InputReader::new(2);
+const USAGE: &str = "\
+usage: day_2 [<file|->] [--policy <count|position|both>] [--graphemes]
 
-    let mut valid_password_count: u32 = 0;
-    for line in reader.lines() {
-        let mut parts = line.split(": ");
-        let rule_string = parts.next().expect("invalid line");
-        let password = parts.next().expect("invalid line");
+Solves both parts, or with --policy counts the passwords valid under the sled rental (count)
+policy, the Toboggan corporate (position) policy or both, from a single read of the input.
+Positions count characters, or with --graphemes what a reader sees as one character.";
 
-        let rule = Rule::from(rule_string);
-        if rule.test(password) {
-            valid_password_count += 1;
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let mut path = None;
+    let mut policies = None;
+    let mut unit = Unit::Scalar;
+    let mut args = args.iter().map(String::as_str);
+    while let Some(arg) = args.next() {
+        match arg {
+            "--policy" => match args.next().map(Policy::selection) {
+                Some(Ok(selection)) => policies = Some(selection),
+                _ => usage(),
+            },
+            "--graphemes" => unit = Unit::Grapheme,
+            path_arg if path.is_none() && !path_arg.starts_with("--") => path = Some(path_arg),
+            _ => usage(),
         }
     }
+    let policies = match policies {
+        Some(policies) => policies,
+        None if unit == Unit::Scalar => return runner::main_for_day(2),
+        None => Policy::ALL.to_vec(),
+    };
 
-    println!("Valid passwords: {}", valid_password_count);
-}
-
-struct Rule {
-    character: char,
-    positions: Vec<usize>,
-}
-
-impl Rule {
-    fn test(&self, password: &str) -> bool {
-        let mut count: u32 = 0;
-        for (index, character) in password.char_indices() {
-            let corporate_position = index + 1;
-            if self.positions.contains(&corporate_position) {
-                if character == self.character {
-                    count += 1;
-                }
+    let database = match runner::open_input(2, path).and_then(day2::parse) {
+        Ok(database) => database,
+        Err(e) => fail(&e),
+    };
+    let mut failure = None;
+    for (policy, count) in day2::count_all(&database, &policies, unit) {
+        match count {
+            Ok(count) => println!(
+                "{} of {} passwords are valid under the {} policy",
+                count,
+                database.entries().len(),
+                policy.name()
+            ),
+            Err(e) => {
+                eprintln!("Day 2 {} policy failed: {}", policy.name(), e);
+                failure = failure.or(Some(e.kind().exit_code()));
             }
         }
-
-        return count == 1;
+    }
+    if let Some(exit_code) = failure {
+        process::exit(exit_code);
     }
 }
 
-impl From<&str> for Rule {
-    fn from(rule_string: &str) -> Rule {
-        if !rule_string.is_ascii() {
-            panic!("invalid rule");
-        }
-
-        let mut parts = rule_string.split_ascii_whitespace();
-
-        let positions_string = parts.next().expect("invalid rule");
-
-        let character: &str = parts.next().expect("invalid rule");
-        let character = character.chars().nth(0).expect("invalid rule");
-
-        let positions = positions_string.split('-');
-        let positions = positions
-            .map(|v| v.parse().expect("invalid rule"))
-            .collect::<Vec<usize>>();
+fn fail(e: &my::Error) -> ! {
+    eprintln!("Day 2 failed: {}", e);
+    process::exit(e.kind().exit_code());
+}
 
-        return Rule {
-            character,
-            positions,
-        };
-    }
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
 }
@@ -1,3 +1,709 @@
+extern crate my;
+
+use my::answers;
+use my::answers::{AnswerKey, Verdict};
+use my::bench;
+use my::days;
+use my::config::Config;
+use my::differential;
+use my::fetch;
+use my::fetch::Fetcher;
+use my::generate;
+use my::input::{InputReader, Inputs};
+use my::pool;
+use my::runner;
+use my::scaffold;
+use my::solution::{Part, Puzzle};
+use my::submit::{Outcome, Submitted, Submitter};
+use std::env;
+use std::fs::File;
+use std::io;
+use std::io::Write;
+use std::path::PathBuf;
+use std::process;
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+
+const USAGE: &str = "\
+usage: aoc run <day|all>... [options] [--format <text|json>] [--parallel] [--jobs <n>]
+       aoc bench <day|all>... [options] [-n <iterations>] [--csv <file>] [--json <file>]
+       aoc verify <day|all>... [options] [--answers <file>] [--record]
+       aoc fetch <day|all>... [options]
+       aoc submit <day> <1|2> [<answer>] [options]
+       aoc new <day>
+       aoc generate <day> [--seed <n>] [--size <n>] [--width <n>] [--seat <id>] [--invalid]
+                          [--output <file>]
+       aoc anonymize <day> [options] [--seed <n>] [--output <file>]
+       aoc difftest <day|all>... [--seeds <n>] [--size <n>]
+
+options:
+    --part <1|2>      only solve one part
+    --inputs <dir>    read inputs from <dir> instead of inputs
+    --profile <name>  use <name>'s inputs, from <dir>/<name>/<year>/day_N, verify takes all
+    --year <year>     use inputs of another event year
+    --input <file|->  read a single day's input from <file>, or stdin
+    --format json     print one JSON object per day and part instead of text
+    --parallel        solve days concurrently, one thread per core, and summarize them
+    --jobs <n>        solve days concurrently on <n> threads
+    --answers <file>  answer key to verify against, defaults to answers in the inputs directory
+    --record          add answers missing from the answer key to it
+
+generate prints a synthetic input, or writes it to <file>, and the answers it should have to
+stderr. --size is the number of entries, --width and --seat pick day 3's map width and day 5's
+free seat, and --invalid plants a defect solving should fail on. anonymize does the same for a
+randomized copy of a day's input that keeps its structure, so it can be shared. difftest solves
+generated inputs with each day's solver and reference solution and prints the first input they
+disagree on, shrunk.
+
+Missing inputs are downloaded using the session token in AOC_SESSION or the config file.";
+
+const DEFAULT_ITERATIONS: usize = 100;
+
 fn main() {
-    panic!("Use the per-day binaries");
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+
+    let code = match args.split_first() {
+        Some((&"run", args)) => run(&Options::parse(args)),
+        Some((&"bench", args)) => bench(&Options::parse(args)),
+        Some((&"verify", args)) => verify(&Options::parse(args)),
+        Some((&"fetch", args)) => fetch(&Options::parse(args)),
+        Some((&"submit", args)) => submit(args),
+        Some((&"new", [day])) => new(day),
+        Some((&"generate", args)) => generate(args),
+        Some((&"anonymize", args)) => anonymize(args),
+        Some((&"difftest", args)) => difftest(args),
+        _ => usage(),
+    };
+    process::exit(code);
+}
+
+/// How `run` prints results.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Format {
+    Text,
+    /// One JSON object per line, see [runner::Report::to_json].
+    Json,
+}
+
+/// Command line options shared by every command.
+struct Options<'a> {
+    format: Format,
+    puzzles: Vec<&'static dyn Puzzle>,
+    parts: Vec<Part>,
+    inputs: Inputs,
+    /// Verify every profile instead of the one in [Options::inputs].
+    all_profiles: bool,
+    input_file: Option<&'a str>,
+    /// Threads to solve days on concurrently, None solves them one after another.
+    threads: Option<usize>,
+    iterations: usize,
+    csv: Option<&'a str>,
+    json: Option<&'a str>,
+    answers: Option<&'a str>,
+    record: bool,
+}
+
+impl<'a> Options<'a> {
+    fn parse(args: &[&'a str]) -> Self {
+        let mut selection = Selection::default();
+        let mut options = Options {
+            format: Format::Text,
+            puzzles: Vec::new(),
+            parts: Part::ALL.to_vec(),
+            inputs: Inputs::new(""),
+            all_profiles: false,
+            input_file: None,
+            threads: None,
+            iterations: DEFAULT_ITERATIONS,
+            csv: None,
+            json: None,
+            answers: None,
+            record: false,
+        };
+
+        let mut args = args.iter().copied();
+        while let Some(arg) = args.next() {
+            if selection.parse(arg, &mut args) {
+                continue;
+            }
+            match arg {
+                "--part" => match args.next().map(str::parse::<Part>) {
+                    Some(Ok(part)) => options.parts = vec![part],
+                    _ => usage(),
+                },
+                "--format" => match value(args.next()) {
+                    "text" => options.format = Format::Text,
+                    "json" => options.format = Format::Json,
+                    _ => usage(),
+                },
+                "--input" => options.input_file = Some(value(args.next())),
+                "--parallel" => options.threads = Some(pool::default_threads()),
+                "--jobs" => match value(args.next()).parse() {
+                    Ok(threads) if threads > 0 => options.threads = Some(threads),
+                    _ => usage(),
+                },
+                "-n" => match value(args.next()).parse() {
+                    Ok(iterations) => options.iterations = iterations,
+                    Err(_) => usage(),
+                },
+                "--csv" => options.csv = Some(value(args.next())),
+                "--json" => options.json = Some(value(args.next())),
+                "--answers" => options.answers = Some(value(args.next())),
+                "--record" => options.record = true,
+                "all" => options.puzzles.extend(days::all()),
+                day => match day.parse::<u8>().ok().and_then(days::get) {
+                    Some(puzzle) => options.puzzles.push(puzzle),
+                    None => {
+                        eprintln!("no solution for day {}", day);
+                        process::exit(2);
+                    }
+                },
+            }
+        }
+
+        // A single input file only makes sense for a single day
+        if options.puzzles.is_empty()
+            || (options.input_file.is_some()
+                && (options.puzzles.len() != 1 || options.threads.is_some()))
+        {
+            usage();
+        }
+        options.all_profiles = selection.profile == Some("all");
+        if options.all_profiles {
+            selection.profile = None;
+        }
+        options.inputs = selection.inputs();
+        return options;
+    }
+
+    fn reader(&self, puzzle: &dyn Puzzle) -> my::Result<InputReader> {
+        self.reader_from(&self.inputs, puzzle)
+    }
+
+    fn reader_from(&self, inputs: &Inputs, puzzle: &dyn Puzzle) -> my::Result<InputReader> {
+        match self.input_file {
+            Some("-") => Ok(InputReader::stdin()),
+            Some(path) => InputReader::from_path(path),
+            None => fetch::open(inputs, puzzle.day()),
+        }
+    }
+}
+
+/// Which inputs to use, given by `--inputs`, `--profile` and `--year` or else the environment.
+#[derive(Default)]
+struct Selection<'a> {
+    root: Option<&'a str>,
+    profile: Option<&'a str>,
+    year: Option<&'a str>,
+}
+
+impl<'a> Selection<'a> {
+    /// Takes `arg`, and its value from `args`, if it is one of the selection options.
+    fn parse<I: Iterator<Item = &'a str>>(&mut self, arg: &str, args: &mut I) -> bool {
+        match arg {
+            "--inputs" => self.root = Some(value(args.next())),
+            "--profile" => self.profile = Some(value(args.next())),
+            "--year" => self.year = Some(value(args.next())),
+            _ => return false,
+        }
+        return true;
+    }
+
+    fn inputs(&self) -> Inputs {
+        let mut inputs = Inputs::from_env().unwrap_or_else(|e| fail("invalid environment", &e));
+        if let Some(root) = self.root {
+            inputs = Inputs::new(root)
+                .with_year(inputs.year())
+                .with_profile(inputs.profile())
+                .unwrap_or_else(|e| fail("invalid profile", &e));
+        }
+        if let Some(profile) = self.profile {
+            inputs = inputs
+                .with_profile(profile)
+                .unwrap_or_else(|e| fail("invalid profile", &e));
+        }
+        if let Some(year) = self.year {
+            match year.parse() {
+                Ok(year) => inputs = inputs.with_year(year),
+                Err(_) => usage(),
+            }
+        }
+        return inputs;
+    }
+}
+
+fn run(options: &Options) -> i32 {
+    if let Some(threads) = options.threads {
+        return run_parallel(options, threads);
+    }
+
+    // Exit with the code of the first failure
+    let mut exit_code = 0;
+    for &puzzle in options.puzzles.iter() {
+        if options.format == Format::Json {
+            let solved = runner::solve(puzzle, options.reader(puzzle), &options.parts);
+            for object in solved.to_json(&options.parts) {
+                println!("{}", object);
+            }
+            if let (Some(e), 0) = (solved.first_error(), exit_code) {
+                exit_code = e.kind().exit_code();
+            }
+            continue;
+        }
+
+        let result = options
+            .reader(puzzle)
+            .and_then(|reader| runner::run(puzzle, reader, &options.parts));
+        if let Err(e) = result {
+            exit_code = report(puzzle, &e, exit_code);
+        }
+    }
+    return exit_code;
+}
+
+/// Exit code of a day that panicked, the same as an uncaught panic's.
+const PANICKED: i32 = 101;
+
+fn run_parallel(options: &Options, threads: usize) -> i32 {
+    let start = Instant::now();
+    let results = runner::solve_all(&options.puzzles, &options.inputs, &options.parts, threads);
+    let elapsed = start.elapsed();
+
+    let mut exit_code = 0;
+    for result in results.iter() {
+        let code = match &result.report {
+            Ok(report) => report.first_error().map_or(0, |e| e.kind().exit_code()),
+            Err(_) => PANICKED,
+        };
+        if exit_code == 0 {
+            exit_code = code;
+        }
+    }
+
+    if options.format == Format::Json {
+        for result in results.iter() {
+            for object in result.to_json(&options.parts) {
+                println!("{}", object);
+            }
+        }
+        return exit_code;
+    }
+
+    for result in results.iter() {
+        if let Ok(report) = &result.report {
+            for solved in report.parts.iter() {
+                if let Ok(answer) = &solved.answer {
+                    println!("Day {} part {}: {}", result.day, solved.part, answer);
+                }
+            }
+        }
+    }
+    println!();
+    if let Err(e) = runner::write_summary(&mut io::stdout(), &results) {
+        eprintln!("failed to write summary: {}", e);
+    }
+    let count = |status| results.iter().filter(|r| r.status() == status).count();
+    println!(
+        "\n{} ok, {} failed, {} panicked in {} on {} threads",
+        count("ok"),
+        count("failed"),
+        count("panicked"),
+        bench::format_duration(elapsed),
+        threads
+    );
+    return exit_code;
+}
+
+fn bench(options: &Options) -> i32 {
+    let mut exit_code = 0;
+    let mut benchmarks = Vec::new();
+    for &puzzle in options.puzzles.iter() {
+        let result = options.reader(puzzle).and_then(|reader| {
+            let source = String::from(reader.source());
+            let input = reader.read_to_string()?;
+            bench::run(puzzle, &input, &source, &options.parts, options.iterations)
+        });
+        match result {
+            Ok(benchmark) => benchmarks.push(benchmark),
+            Err(e) => exit_code = report(puzzle, &e, exit_code),
+        }
+    }
+
+    let mut written = bench::write_table(&mut io::stdout(), &benchmarks);
+    if let Some(path) = options.csv {
+        written = written.and_then(|_| bench::write_csv(&mut create(path)?, &benchmarks));
+    }
+    if let Some(path) = options.json {
+        written = written.and_then(|_| {
+            writeln!(create(path)?, "{}", bench::to_json(&benchmarks))?;
+            Ok(())
+        });
+    }
+
+    if let Err(e) = written {
+        eprintln!("failed to write results: {}", e);
+        if exit_code == 0 {
+            exit_code = e.kind().exit_code();
+        }
+    }
+    return exit_code;
+}
+
+/// Exit code when any answer does not match the answer key.
+const VERIFY_FAILED: i32 = 1;
+
+fn verify(options: &Options) -> i32 {
+    let path = match options.answers {
+        Some(path) => PathBuf::from(path),
+        None => options.inputs.answers_path(),
+    };
+    let mut key = match AnswerKey::load(&path) {
+        Ok(key) => key,
+        Err(e) => {
+            eprintln!("failed to load answers: {}", e);
+            return e.kind().exit_code();
+        }
+    };
+
+    let profiles = if options.all_profiles {
+        match options.inputs.profiles() {
+            Ok(names) => names
+                .iter()
+                .map(|name| options.inputs.clone().with_profile(name))
+                .collect::<my::Result<Vec<_>>>(),
+            Err(e) => Err(e),
+        }
+    } else {
+        Ok(vec![options.inputs.clone()])
+    };
+    let profiles = profiles.unwrap_or_else(|e| fail("failed to list profiles", &e));
+
+    let (mut passed, mut failed, mut missing) = (0, 0, 0);
+    let mut recorded = 0;
+    for inputs in profiles.iter() {
+        for &puzzle in options.puzzles.iter() {
+            let profile = inputs.profile();
+            let reader = options.reader_from(inputs, puzzle);
+            let year = inputs.year();
+            for check in answers::verify(puzzle, reader, &key, profile, year, &options.parts) {
+                if options.all_profiles {
+                    print!("{}: ", profile);
+                }
+                println!("{}", check);
+                match (&check.verdict, &check.answer) {
+                    (Verdict::Pass, _) => passed += 1,
+                    (Verdict::Missing, Some(answer)) if options.record => {
+                        missing += 1;
+                        key.insert(profile, year, check.day, check.part, &answer.to_string());
+                        recorded += 1;
+                    }
+                    (Verdict::Missing, _) | (Verdict::Unsolved, _) => missing += 1,
+                    _ => failed += 1,
+                }
+            }
+        }
+    }
+    println!("{} passed, {} failed, {} missing", passed, failed, missing);
+
+    if recorded != 0 {
+        if let Err(e) = key.save(&path) {
+            eprintln!("failed to record answers: {}", e);
+            return e.kind().exit_code();
+        }
+        println!("recorded {} answers in {}", recorded, path.display());
+    }
+
+    if failed != 0 {
+        return VERIFY_FAILED;
+    }
+    return 0;
+}
+
+fn fetch(options: &Options) -> i32 {
+    let fetcher = match Config::from_env() {
+        Ok(config) => Fetcher::new(config, options.inputs.clone()),
+        Err(e) => {
+            eprintln!("failed to load config: {}", e);
+            return e.kind().exit_code();
+        }
+    };
+
+    let mut exit_code = 0;
+    for &puzzle in options.puzzles.iter() {
+        match fetcher.fetch(puzzle.day()) {
+            Ok(path) => println!("Day {}: {}", puzzle.day(), path.display()),
+            Err(e) => exit_code = report(puzzle, &e, exit_code),
+        }
+    }
+    return exit_code;
+}
+
+/// Exit code when the site does not accept a submitted answer.
+const SUBMIT_REJECTED: i32 = 1;
+
+/// Submits the given answer, or the computed one, for one part of one day.
+fn submit(args: &[&str]) -> i32 {
+    let mut selection = Selection::default();
+    let mut positional = Vec::new();
+    let mut args = args.iter().copied();
+    while let Some(arg) = args.next() {
+        if !selection.parse(arg, &mut args) {
+            positional.push(arg);
+        }
+    }
+    let inputs = selection.inputs();
+    let (puzzle, part, answer) = match positional.as_slice() {
+        [day, part, rest @ ..] if rest.len() <= 1 => {
+            match (day.parse().ok().and_then(days::get), part.parse::<Part>()) {
+                (Some(puzzle), Ok(part)) => (puzzle, part, rest.first().copied()),
+                _ => usage(),
+            }
+        }
+        _ => usage(),
+    };
+
+    let result = Config::from_env().and_then(|config| {
+        let answer = match answer {
+            Some(answer) => String::from(answer),
+            None => {
+                let input = puzzle.parse(fetch::open(&inputs, puzzle.day())?)?;
+                puzzle.solve(input.as_ref(), part)?.to_string()
+            }
+        };
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |since| since.as_secs());
+        let submitted = Submitter::new(config, &inputs)?.submit(puzzle.day(), part, &answer, now)?;
+        return Ok((answer, submitted));
+    });
+
+    match result {
+        Ok((answer, submitted)) => {
+            print!("Day {} part {}: {} is {}", puzzle.day(), part, answer, submitted.outcome());
+            match &submitted {
+                Submitted::Sent(reply) => println!("\n{}", reply.message),
+                Submitted::Known(_) => println!(" (from the submission log, not sent)"),
+            }
+            if submitted.outcome() == Outcome::Correct {
+                return 0;
+            }
+            return SUBMIT_REJECTED;
+        }
+        Err(e) => return report(puzzle, &e, 0),
+    }
+}
+
+/// Scaffolds `day` in the repository in the current directory.
+fn new(day: &str) -> i32 {
+    let day = match day.parse() {
+        Ok(day) => day,
+        Err(_) => usage(),
+    };
+    match scaffold::new_day(".", day) {
+        Ok(paths) => {
+            for path in paths {
+                println!("wrote {}", path.display());
+            }
+            return 0;
+        }
+        Err(e) => {
+            eprintln!("failed to create day {}: {}", day, e);
+            return e.kind().exit_code();
+        }
+    }
+}
+
+/// Prints a generated input for one day and the answers it should have.
+fn generate(args: &[&str]) -> i32 {
+    let mut params = generate::Params::default();
+    let mut seed = None;
+    let mut output = None;
+    let mut day = None;
+    let mut args = args.iter().copied();
+    while let Some(arg) = args.next() {
+        match arg {
+            "--seed" => seed = Some(number(args.next())),
+            "--size" => params.size = number(args.next()),
+            "--width" => params.width = Some(number(args.next())),
+            "--seat" => params.seat = Some(number(args.next())),
+            "--invalid" => params.invalid = true,
+            "--output" => output = Some(value(args.next())),
+            arg => match arg.parse().ok().filter(|_| day.is_none()) {
+                Some(arg) => day = Some(arg),
+                None => usage(),
+            },
+        }
+    }
+    let day = day.unwrap_or_else(|| usage());
+    let generator = match generate::get(day) {
+        Some(generator) => generator,
+        None => {
+            eprintln!("day {} has no input generator", day);
+            return 2;
+        }
+    };
+    if params.invalid && !generator.can_invalidate() {
+        eprintln!("day {} accepts every input, ignoring --invalid", day);
+    }
+
+    let seed = seed.unwrap_or_else(clock_seed);
+    let generated = generator.generate(&mut generate::Rng::new(seed), &params);
+    return write_generated(day, seed, &generated, output);
+}
+
+/// Prints a randomized copy of one day's input and its answers.
+fn anonymize(args: &[&str]) -> i32 {
+    let mut selection = Selection::default();
+    let mut input_file = None;
+    let mut seed = None;
+    let mut output = None;
+    let mut puzzle = None;
+    let mut args = args.iter().copied();
+    while let Some(arg) = args.next() {
+        if selection.parse(arg, &mut args) {
+            continue;
+        }
+        match arg {
+            "--input" => input_file = Some(value(args.next())),
+            "--seed" => seed = Some(number(args.next())),
+            "--output" => output = Some(value(args.next())),
+            day => match day.parse().ok().and_then(days::get) {
+                Some(day) if puzzle.is_none() => puzzle = Some(day),
+                _ => usage(),
+            },
+        }
+    }
+    let puzzle = puzzle.unwrap_or_else(|| usage());
+
+    let reader = match input_file {
+        Some("-") => Ok(InputReader::stdin()),
+        Some(path) => InputReader::from_path(path),
+        None => fetch::open(&selection.inputs(), puzzle.day()),
+    };
+    let seed = seed.unwrap_or_else(clock_seed);
+    let generated = reader
+        .and_then(InputReader::read_to_string)
+        .and_then(|text| generate::anonymize(puzzle.day(), seed, &text));
+    match generated {
+        Ok(generated) => return write_generated(puzzle.day(), seed, &generated, output),
+        Err(e) => return report(puzzle, &e, 0),
+    }
+}
+
+/// Exit code when a solver and its reference solution disagree.
+const DIVERGED: i32 = 1;
+
+/// Default number of generated inputs `difftest` compares solvers on.
+const DEFAULT_SEEDS: u64 = 100;
+
+/// Compares solvers with their reference solutions on generated inputs.
+fn difftest(args: &[&str]) -> i32 {
+    let mut params = generate::Params::default();
+    let mut seeds = DEFAULT_SEEDS;
+    let mut selected = Vec::new();
+    let mut args = args.iter().copied();
+    while let Some(arg) = args.next() {
+        match arg {
+            "--seeds" => seeds = number(args.next()),
+            "--size" => params.size = number(args.next()),
+            "all" => selected.extend(generate::all().iter().map(|g| g.day())),
+            day => match day.parse().ok().filter(|&day| generate::get(day).is_some()) {
+                Some(day) => selected.push(day),
+                None => {
+                    eprintln!("day {} has no input generator", day);
+                    usage();
+                }
+            },
+        }
+    }
+    if selected.is_empty() {
+        usage();
+    }
+
+    let mut exit_code = 0;
+    for day in selected {
+        match differential::check(day, 0..seeds, &params) {
+            Ok(None) => println!("Day {}: solver and reference agree on {} inputs", day, seeds),
+            Ok(Some(divergence)) => {
+                println!("{}", divergence);
+                exit_code = DIVERGED;
+            }
+            Err(e) => println!("Day {} skipped: {}", day, e),
+        }
+    }
+    return exit_code;
+}
+
+/// A seed for when none is given, printed with the input so it can be made again.
+fn clock_seed() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |since| since.as_nanos() as u64)
+}
+
+/// Writes a generated input to `output`, or stdout, and what it should produce to stderr.
+fn write_generated(
+    day: u8,
+    seed: u64,
+    generated: &generate::Generated,
+    output: Option<&str>,
+) -> i32 {
+    let written = match output {
+        Some(path) => create(path).and_then(|mut file| {
+            file.write_all(generated.text.as_bytes())
+                .map_err(|e| my::Error::io(format!("could not write {}", path)).with_source(e))
+        }),
+        None => io::stdout()
+            .write_all(generated.text.as_bytes())
+            .map_err(|e| my::Error::io("could not write the input").with_source(e)),
+    };
+    if let Err(e) = written {
+        fail("failed to write the input", &e);
+    }
+
+    eprintln!("Day {} seed {}", day, seed);
+    for part in Part::ALL.iter().copied() {
+        eprintln!("Day {} part {}: {}", day, part, generated.expected(part));
+    }
+    return 0;
+}
+
+fn create(path: &str) -> my::Result<File> {
+    File::create(path)
+        .map_err(|e| my::Error::io(format!("could not create {}", path)).with_source(e))
+}
+
+/// Prints `e` and returns the exit code to use, keeping the first failure's.
+fn report(puzzle: &dyn Puzzle, e: &my::Error, exit_code: i32) -> i32 {
+    eprintln!("Day {} failed: {}", puzzle.day(), e);
+    if exit_code == 0 {
+        e.kind().exit_code()
+    } else {
+        exit_code
+    }
+}
+
+/// Reports a failure that prevents the command from doing anything and exits.
+fn fail(what: &str, e: &my::Error) -> ! {
+    eprintln!("{}: {}", what, e);
+    process::exit(e.kind().exit_code());
+}
+
+fn value(arg: Option<&str>) -> &str {
+    match arg {
+        Some(arg) => arg,
+        None => usage(),
+    }
+}
+
+fn number<T: std::str::FromStr>(arg: Option<&str>) -> T {
+    match value(arg).parse() {
+        Ok(number) => number,
+        Err(_) => usage(),
+    }
+}
+
+fn usage() -> ! {
+    eprintln!("{}", USAGE);
+    process::exit(2);
 }
@@ -0,0 +1,316 @@
+use crate::input::InputReader;
+use crate::solution::{Answer, Part, Puzzle};
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+/// Profile answers are recorded under when no other is chosen.
+pub const DEFAULT_PROFILE: &str = "default";
+
+/// File name of the answer key within an inputs root.
+pub const ANSWERS_FILE: &str = "answers";
+
+/// Known correct answers, per input profile, year, day and part.
+///
+/// Stored as text, one `<profile> <year> <day> <part> <answer>` entry per line. Lines without a
+/// year, from before inputs were kept per year, are for [crate::config::YEAR]. Blank lines and
+/// lines starting with `#` are ignored.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct AnswerKey {
+    entries: BTreeMap<(String, u16, u8, Part), String>,
+}
+
+impl AnswerKey {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Loads the key at `path`, a missing file is an empty key.
+    pub fn load<P: AsRef<Path>>(path: P) -> crate::Result<Self> {
+        let path = path.as_ref();
+        match InputReader::from_path(path) {
+            Ok(reader) => Self::parse(reader),
+            Err(_) if !path.exists() => Ok(Self::new()),
+            Err(e) => Err(e),
+        }
+    }
+
+    pub fn parse(reader: InputReader) -> crate::Result<Self> {
+        let mut key = Self::new();
+        let mut lines = reader.lines();
+        while let Some(line) = lines.next() {
+            let line = line?;
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+
+            let fields: Vec<&str> = line.split_whitespace().collect();
+            // Lines without a year are from before the key had one
+            let fields = match fields.as_slice() {
+                [profile, year, day, part, answer] => {
+                    Some((*profile, year.parse().ok(), *day, *part, *answer))
+                }
+                [profile, day, part, answer] => {
+                    Some((*profile, Some(crate::config::YEAR), *day, *part, *answer))
+                }
+                _ => None,
+            };
+            let entry = fields.and_then(|(profile, year, day, part, answer)| {
+                match (year, day.parse(), part.parse()) {
+                    (Some(year), Ok(day), Ok(part)) => Some((profile, year, day, part, answer)),
+                    _ => None,
+                }
+            });
+
+            match entry {
+                Some((profile, year, day, part, answer)) => {
+                    key.insert(profile, year, day, part, answer)
+                }
+                None => {
+                    return Err(crate::Error::parse(
+                        "expected '<profile> <year> <day> <part> <answer>'",
+                    )
+                    .with_line(lines.line_number(), &line)
+                    .with_file(lines.source()))
+                }
+            }
+        }
+        return Ok(key);
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> crate::Result<()> {
+        let path = path.as_ref();
+        fs::write(path, self.to_string()).map_err(|e: io::Error| {
+            crate::Error::io(format!("could not write {}", path.display())).with_source(e)
+        })
+    }
+
+    pub fn get(&self, profile: &str, year: u16, day: u8, part: Part) -> Option<&str> {
+        self.entries
+            .get(&(String::from(profile), year, day, part))
+            .map(String::as_str)
+    }
+
+    pub fn insert(&mut self, profile: &str, year: u16, day: u8, part: Part, answer: &str) {
+        self.entries.insert(
+            (String::from(profile), year, day, part),
+            String::from(answer),
+        );
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+impl fmt::Display for AnswerKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "# profile year day part answer")?;
+        for ((profile, year, day, part), answer) in self.entries.iter() {
+            writeln!(f, "{} {} {} {} {}", profile, year, day, part, answer)?;
+        }
+        return Ok(());
+    }
+}
+
+/// Outcome of checking one part against the [AnswerKey].
+#[derive(Debug)]
+pub enum Verdict {
+    Pass,
+    Fail { expected: String },
+    /// No answer is recorded for the part.
+    Missing,
+    /// The part has no solver, see [Answer::Unsolved].
+    Unsolved,
+    /// Solving failed before there was an answer to check.
+    Error(crate::Error),
+}
+
+impl Verdict {
+    /// Whether this verdict means the solution is wrong or broken.
+    pub fn is_failure(&self) -> bool {
+        matches!(self, Verdict::Fail { .. } | Verdict::Error(_))
+    }
+}
+
+/// One checked part.
+#[derive(Debug)]
+pub struct Check {
+    pub day: u8,
+    pub part: Part,
+    pub answer: Option<Answer>,
+    pub verdict: Verdict,
+}
+
+impl fmt::Display for Check {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Day {} part {}: ", self.day, self.part)?;
+        match (&self.verdict, &self.answer) {
+            (Verdict::Pass, Some(answer)) => write!(f, "PASS {}", answer),
+            (Verdict::Fail { expected }, Some(answer)) => {
+                write!(f, "FAIL got {}, expected {}", answer, expected)
+            }
+            (Verdict::Missing, Some(answer)) => write!(f, "MISSING got {}", answer),
+            (Verdict::Unsolved, _) => write!(f, "MISSING unsolved"),
+            (Verdict::Error(e), _) => write!(f, "ERROR {}", e),
+            (_, None) => write!(f, "ERROR no answer"),
+        }
+    }
+}
+
+/// Solves `parts` of `puzzle` and checks each answer against `key`'s answers for `profile` in
+/// `year`.
+///
+/// A parse failure fails every part.
+pub fn verify(
+    puzzle: &dyn Puzzle,
+    reader: crate::Result<InputReader>,
+    key: &AnswerKey,
+    profile: &str,
+    year: u16,
+    parts: &[Part],
+) -> Vec<Check> {
+    let day = puzzle.day();
+    let input = reader.and_then(|reader| puzzle.parse(reader));
+
+    let mut checks = Vec::new();
+    for &part in parts {
+        let answer = match &input {
+            Ok(input) => puzzle.solve(input.as_ref(), part),
+            Err(e) => Err(crate::Error::new(e.kind(), e.to_string())),
+        };
+
+        let check = match answer {
+            Ok(Answer::Unsolved) => Check {
+                day,
+                part,
+                answer: Some(Answer::Unsolved),
+                verdict: Verdict::Unsolved,
+            },
+            Ok(answer) => {
+                let verdict = match key.get(profile, year, day, part) {
+                    Some(expected) if expected == answer.to_string() => Verdict::Pass,
+                    Some(expected) => Verdict::Fail {
+                        expected: String::from(expected),
+                    },
+                    None => Verdict::Missing,
+                };
+                Check {
+                    day,
+                    part,
+                    answer: Some(answer),
+                    verdict,
+                }
+            }
+            Err(e) => Check {
+                day,
+                part,
+                answer: None,
+                verdict: Verdict::Error(e),
+            },
+        };
+        checks.push(check);
+    }
+    return checks;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    fn reader(s: &str) -> InputReader {
+        InputReader::from_text(String::from(s), String::from("test"))
+    }
+
+    #[test]
+    fn test_round_trips_through_text() {
+        let mut key = AnswerKey::new();
+        key.insert("default", 2020, 5, Part::Two, "651");
+        key.insert("alice", 2020, 1, Part::One, "1007104");
+        key.insert("alice", 2019, 1, Part::One, "3303995");
+
+        let parsed = AnswerKey::parse(reader(&key.to_string())).unwrap();
+        assert_eq!(parsed, key);
+        assert_eq!(parsed.get("default", 2020, 5, Part::Two), Some("651"));
+        assert_eq!(parsed.get("default", 2020, 5, Part::One), None);
+        assert_eq!(parsed.get("alice", 2019, 1, Part::One), Some("3303995"));
+        assert_eq!(parsed.get("default", 2019, 5, Part::Two), None);
+    }
+
+    #[test]
+    fn test_reads_lines_without_a_year() {
+        let key = AnswerKey::parse(reader("default 5 2 651\ndefault 2019 5 2 7\n")).unwrap();
+        assert_eq!(
+            key.get("default", crate::config::YEAR, 5, Part::Two),
+            Some("651")
+        );
+        assert_eq!(key.get("default", 2019, 5, Part::Two), Some("7"));
+    }
+
+    #[test]
+    fn test_rejects_malformed_lines() {
+        let e = AnswerKey::parse(reader("default 5 2 651\ndefault five 2 651\n")).unwrap_err();
+        assert_eq!(e.line_number(), Some(2));
+        let e = AnswerKey::parse(reader("default 20x0 5 2 651\n")).unwrap_err();
+        assert_eq!(e.line_number(), Some(1));
+    }
+
+    #[test]
+    fn test_verify_reports_each_verdict() {
+        let puzzle = days::get(5).unwrap();
+        let input = "FFFFFFFLLL\nFFFFFFFLRL\n";
+        let mut key = AnswerKey::new();
+        key.insert(DEFAULT_PROFILE, 2020, 5, Part::One, "3");
+
+        let checks = verify(
+            puzzle,
+            Ok(reader(input)),
+            &key,
+            DEFAULT_PROFILE,
+            2020,
+            &Part::ALL,
+        );
+        assert!(matches!(checks[0].verdict, Verdict::Fail { .. }));
+        assert!(matches!(checks[1].verdict, Verdict::Missing));
+
+        key.insert(DEFAULT_PROFILE, 2020, 5, Part::One, "2");
+        let checks = verify(
+            puzzle,
+            Ok(reader(input)),
+            &key,
+            DEFAULT_PROFILE,
+            2020,
+            &Part::ALL,
+        );
+        assert!(matches!(checks[0].verdict, Verdict::Pass));
+
+        // Another year's answers are not this year's
+        let checks = verify(
+            puzzle,
+            Ok(reader(input)),
+            &key,
+            DEFAULT_PROFILE,
+            2019,
+            &Part::ALL,
+        );
+        assert!(matches!(checks[0].verdict, Verdict::Missing));
+    }
+
+    #[test]
+    fn test_verify_fails_every_part_on_bad_input() {
+        let puzzle = days::get(5).unwrap();
+        let checks = verify(
+            puzzle,
+            Ok(reader("nope\n")),
+            &AnswerKey::new(),
+            "x",
+            2020,
+            &Part::ALL,
+        );
+        assert_eq!(checks.len(), 2);
+        assert!(checks.iter().all(|c| c.verdict.is_failure()));
+    }
+}
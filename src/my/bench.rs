@@ -0,0 +1,232 @@
+use crate::input::InputReader;
+use crate::json;
+use crate::solution::{Part, Puzzle};
+use std::fmt;
+use std::hint::black_box;
+use std::io::Write;
+use std::time::{Duration, Instant};
+
+/// Summary of repeated timings of one phase.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Stats {
+    pub samples: usize,
+    pub min: Duration,
+    pub median: Duration,
+    pub mean: Duration,
+    pub max: Duration,
+}
+
+impl Stats {
+    /// Summarizes `samples`, or None if there are none.
+    pub fn from_samples(mut samples: Vec<Duration>) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        samples.sort();
+        let n = samples.len();
+        let median = if n % 2 == 1 {
+            samples[n / 2]
+        } else {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        };
+        let total: Duration = samples.iter().sum();
+
+        return Some(Self {
+            samples: n,
+            min: samples[0],
+            median,
+            mean: total / n as u32,
+            max: samples[n - 1],
+        });
+    }
+}
+
+/// A timed step of solving a day.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Phase {
+    Parse,
+    Solve(Part),
+}
+
+impl fmt::Display for Phase {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Phase::Parse => write!(f, "parse"),
+            Phase::Solve(part) => write!(f, "part{}", part),
+        }
+    }
+}
+
+/// Timings of every phase of one day.
+#[derive(Clone, Debug)]
+pub struct Benchmark {
+    pub day: u8,
+    pub phases: Vec<(Phase, Stats)>,
+}
+
+/// Times `puzzle` on `input`, parsing and then solving each of `parts` `iterations` times.
+///
+/// The input is held in memory so file system access is not part of the parse timings.
+pub fn run(
+    puzzle: &dyn Puzzle,
+    input: &str,
+    source: &str,
+    parts: &[Part],
+    iterations: usize,
+) -> crate::Result<Benchmark> {
+    let iterations = iterations.max(1);
+    let reader = || InputReader::from_text(String::from(input), String::from(source));
+
+    let mut samples = Vec::with_capacity(iterations);
+    let mut parsed = None;
+    for _ in 0..iterations {
+        let reader = reader();
+        let start = Instant::now();
+        let result = puzzle.parse(reader)?;
+        samples.push(start.elapsed());
+        parsed = Some(result);
+    }
+    let parsed = parsed.unwrap();
+
+    let mut phases = Vec::new();
+    phases.push((Phase::Parse, Stats::from_samples(samples).unwrap()));
+
+    for &part in parts {
+        let mut samples = Vec::with_capacity(iterations);
+        for _ in 0..iterations {
+            let start = Instant::now();
+            black_box(puzzle.solve(parsed.as_ref(), part)?);
+            samples.push(start.elapsed());
+        }
+        phases.push((Phase::Solve(part), Stats::from_samples(samples).unwrap()));
+    }
+
+    return Ok(Benchmark {
+        day: puzzle.day(),
+        phases,
+    });
+}
+
+/// Writes a human readable table of `benchmarks`.
+pub fn write_table<W: Write>(out: &mut W, benchmarks: &[Benchmark]) -> crate::Result<()> {
+    writeln!(
+        out,
+        "{:>3}  {:<6} {:>7} {:>10} {:>10} {:>10} {:>10}",
+        "day", "phase", "samples", "min", "median", "mean", "max"
+    )?;
+    for benchmark in benchmarks {
+        for (phase, stats) in benchmark.phases.iter() {
+            writeln!(
+                out,
+                "{:>3}  {:<6} {:>7} {:>10} {:>10} {:>10} {:>10}",
+                benchmark.day,
+                phase.to_string(),
+                stats.samples,
+                format_duration(stats.min),
+                format_duration(stats.median),
+                format_duration(stats.mean),
+                format_duration(stats.max),
+            )?;
+        }
+    }
+    return Ok(());
+}
+
+/// Writes `benchmarks` as CSV, one row per phase with times in nanoseconds.
+pub fn write_csv<W: Write>(out: &mut W, benchmarks: &[Benchmark]) -> crate::Result<()> {
+    writeln!(out, "day,phase,samples,min_ns,median_ns,mean_ns,max_ns")?;
+    for benchmark in benchmarks {
+        for (phase, stats) in benchmark.phases.iter() {
+            writeln!(
+                out,
+                "{},{},{},{},{},{},{}",
+                benchmark.day,
+                phase,
+                stats.samples,
+                stats.min.as_nanos(),
+                stats.median.as_nanos(),
+                stats.mean.as_nanos(),
+                stats.max.as_nanos(),
+            )?;
+        }
+    }
+    return Ok(());
+}
+
+/// `benchmarks` as a JSON array of the same rows [write_csv] produces.
+pub fn to_json(benchmarks: &[Benchmark]) -> json::Value {
+    let mut rows = Vec::new();
+    for benchmark in benchmarks {
+        for (phase, stats) in benchmark.phases.iter() {
+            rows.push(
+                json::Value::object()
+                    .with("day", benchmark.day)
+                    .with("phase", phase.to_string())
+                    .with("samples", stats.samples)
+                    .with("min_ns", nanos(stats.min))
+                    .with("median_ns", nanos(stats.median))
+                    .with("mean_ns", nanos(stats.mean))
+                    .with("max_ns", nanos(stats.max)),
+            );
+        }
+    }
+    return json::Value::Array(rows);
+}
+
+/// `d` in whole nanoseconds, the unit machine readable timings are reported in.
+pub(crate) fn nanos(d: Duration) -> u64 {
+    d.as_nanos() as u64
+}
+
+/// Formats `d` with a unit that keeps it short, e.g. `12.3µs`.
+pub fn format_duration(d: Duration) -> String {
+    let nanos = d.as_nanos();
+    if nanos < 1_000 {
+        format!("{}ns", nanos)
+    } else if nanos < 1_000_000 {
+        format!("{:.1}µs", nanos as f64 / 1e3)
+    } else if nanos < 1_000_000_000 {
+        format!("{:.1}ms", nanos as f64 / 1e6)
+    } else {
+        format!("{:.2}s", nanos as f64 / 1e9)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(v: u64) -> Duration {
+        Duration::from_millis(v)
+    }
+
+    #[test]
+    fn test_stats_odd_samples() {
+        let stats = Stats::from_samples(vec![ms(5), ms(1), ms(3)]).unwrap();
+        assert_eq!(stats.samples, 3);
+        assert_eq!(stats.min, ms(1));
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.mean, ms(3));
+        assert_eq!(stats.max, ms(5));
+    }
+
+    #[test]
+    fn test_stats_even_samples_average_the_middle() {
+        let stats = Stats::from_samples(vec![ms(4), ms(1), ms(2), ms(10)]).unwrap();
+        assert_eq!(stats.median, ms(3));
+        assert_eq!(stats.mean, Duration::from_micros(4250));
+    }
+
+    #[test]
+    fn test_stats_need_samples() {
+        assert_eq!(Stats::from_samples(Vec::new()), None);
+    }
+
+    #[test]
+    fn test_format_duration_units() {
+        assert_eq!(format_duration(Duration::from_nanos(12)), "12ns");
+        assert_eq!(format_duration(Duration::from_nanos(12_340)), "12.3µs");
+        assert_eq!(format_duration(ms(1500)), "1.50s");
+    }
+}
@@ -0,0 +1,185 @@
+//! Settings for talking to the puzzle site, read from the environment and a config file.
+//!
+//! The config file holds `key = value` lines, blank lines and lines starting with `#` are
+//! ignored:
+//!
+//! ```text
+//! session = 53616c7465645f5f...
+//! session.alice = 6e6f7420612072...
+//! base_url = https://adventofcode.com
+//! ```
+//!
+//! `session.<profile>` is the token of the person whose inputs are kept under that
+//! [profile](crate::input::Inputs::with_profile).
+
+use crate::answers::DEFAULT_PROFILE;
+use crate::http::Url;
+use std::collections::BTreeMap;
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+/// Environment variable holding the session token, overrides the config file.
+pub const SESSION_ENV: &str = "AOC_SESSION";
+
+/// Environment variable holding the site's base URL, overrides the config file.
+pub const BASE_URL_ENV: &str = "AOC_BASE_URL";
+
+/// Environment variable naming the config file, instead of `$HOME/.config/aoc/config`.
+pub const CONFIG_ENV: &str = "AOC_CONFIG";
+
+pub const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+
+/// The event year these puzzles are from.
+pub const YEAR: u16 = 2020;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Config {
+    pub base_url: String,
+    /// The `session` cookie of a logged in user.
+    pub session: Option<String>,
+    /// Session cookies of other profiles, by profile name.
+    pub sessions: BTreeMap<String, String>,
+    /// Whether `session` is used for every profile, as when it comes from [SESSION_ENV].
+    pub shared_session: bool,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            base_url: String::from(DEFAULT_BASE_URL),
+            session: None,
+            sessions: BTreeMap::new(),
+            shared_session: false,
+        }
+    }
+}
+
+impl Config {
+    /// Reads the config file, if there is one, then applies the environment overrides.
+    pub fn from_env() -> crate::Result<Self> {
+        let mut config = match config_path() {
+            Some(path) if path.exists() => {
+                let text = fs::read_to_string(&path).map_err(|e| {
+                    crate::Error::io(format!("could not read {}", path.display())).with_source(e)
+                })?;
+                Self::parse(&text).map_err(|e| e.with_file(path.display().to_string()))?
+            }
+            _ => Self::default(),
+        };
+
+        // An explicit token is meant for whichever profile is in use
+        if let Ok(session) = env::var(SESSION_ENV) {
+            config.session = Some(session);
+            config.sessions.clear();
+            config.shared_session = true;
+        }
+        if let Ok(base_url) = env::var(BASE_URL_ENV) {
+            config.base_url = base_url;
+        }
+        return Ok(config);
+    }
+
+    /// Parses the text of a config file.
+    pub fn parse(text: &str) -> crate::Result<Self> {
+        let mut config = Self::default();
+        for (i, line) in text.lines().enumerate() {
+            let trimmed = line.trim();
+            if trimmed.is_empty() || trimmed.starts_with('#') {
+                continue;
+            }
+
+            match trimmed.split_once('=') {
+                Some((key, value)) => match key.trim() {
+                    "session" => config.session = Some(String::from(value.trim())),
+                    "base_url" => config.base_url = String::from(value.trim()),
+                    key if key.starts_with("session.") => {
+                        let profile = &key["session.".len()..];
+                        config
+                            .sessions
+                            .insert(String::from(profile), String::from(value.trim()));
+                    }
+                    key => {
+                        return Err(crate::Error::parse(format!("unknown setting '{}'", key))
+                            .with_line(i + 1, line))
+                    }
+                },
+                None => {
+                    return Err(
+                        crate::Error::parse("expected '<key> = <value>'").with_line(i + 1, line)
+                    )
+                }
+            }
+        }
+        return Ok(config);
+    }
+
+    pub fn base_url(&self) -> crate::Result<Url> {
+        Url::parse(&self.base_url)
+    }
+
+    /// The session token for `profile`, or an error explaining how to configure one.
+    ///
+    /// Profiles without a token of their own only fall back to the plain `session` if they are
+    /// the default profile, so nobody's inputs are downloaded with someone else's token.
+    pub fn session(&self, profile: &str) -> crate::Result<&str> {
+        let session = match self.sessions.get(profile) {
+            Some(session) => Some(session.as_str()),
+            None if self.shared_session || profile == DEFAULT_PROFILE => {
+                self.session.as_deref()
+            }
+            None => None,
+        };
+        match session {
+            Some(session) if !session.is_empty() => Ok(session),
+            _ => Err(crate::Error::validation(format!(
+                "no session token configured for profile '{}', set {} or 'session.{}' in the \
+                 config file",
+                profile, SESSION_ENV, profile
+            ))),
+        }
+    }
+}
+
+/// Where the config file is looked for.
+pub fn config_path() -> Option<PathBuf> {
+    if let Some(path) = env::var_os(CONFIG_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    let home = env::var_os("HOME")?;
+    return Some(PathBuf::from(home).join(".config/aoc/config"));
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_settings() {
+        let config =
+            Config::parse("# mine\nsession = abc\n\nbase_url=http://localhost:1\n").unwrap();
+        assert_eq!(config.session(DEFAULT_PROFILE).unwrap(), "abc");
+        assert_eq!(config.base_url, "http://localhost:1");
+    }
+
+    #[test]
+    fn test_profiles_need_their_own_session() {
+        let config = Config::parse("session = mine\nsession.alice = hers\n").unwrap();
+        assert_eq!(config.session(DEFAULT_PROFILE).unwrap(), "mine");
+        assert_eq!(config.session("alice").unwrap(), "hers");
+        assert!(config.session("bob").is_err());
+
+        let shared = Config {
+            shared_session: true,
+            ..config
+        };
+        assert_eq!(shared.session("bob").unwrap(), "mine");
+    }
+
+    #[test]
+    fn test_rejects_unknown_settings() {
+        let e = Config::parse("session = abc\ncolour = blue\n").unwrap_err();
+        assert_eq!(e.line_number(), Some(2));
+        assert!(Config::default().session(DEFAULT_PROFILE).is_err());
+    }
+}
@@ -0,0 +1,27 @@
+use crate::solution::Puzzle;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+
+static DAYS: [&dyn Puzzle; 6] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+];
+
+/// Every implemented day, in calendar order.
+pub fn all() -> &'static [&'static dyn Puzzle] {
+    &DAYS
+}
+
+/// Looks up the puzzle for `day`, or None if it has not been implemented.
+pub fn get(day: u8) -> Option<&'static dyn Puzzle> {
+    DAYS.iter().copied().find(|p| p.day() == day)
+}
@@ -0,0 +1,476 @@
+//! Day 1: Report Repair, find the expense report entries that sum to 2020.
+
+use crate::input::InputReader;
+use crate::solution::{Answer, Part, Solution};
+use std::collections::HashMap;
+use std::convert::TryFrom;
+
+/// A single expense report entry.
+pub type InputType = i32;
+
+/// The year every expense report is reconciled against.
+pub const SHITHOLE_YEAR: InputType = 2020;
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<InputType>;
+
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn parse(&self, reader: InputReader) -> crate::Result<Self::Input> {
+        collect_inputs(reader)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> crate::Result<Answer> {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Self::Input) -> crate::Result<Answer> {
+        part_two(input)
+    }
+
+    fn reference(&self, input: &Self::Input, part: Part) -> Option<crate::Result<Answer>> {
+        let k = match part {
+            Part::One => 2,
+            Part::Two => 3,
+        };
+        return Some(reference_sum(input, k, SHITHOLE_YEAR));
+    }
+}
+
+/// Reads one expense report entry per line.
+pub fn collect_inputs(reader: InputReader) -> crate::Result<Vec<InputType>> {
+    reader.parse_lines::<InputType>().collect()
+}
+
+/// The product of the two entries summing to [SHITHOLE_YEAR].
+pub fn part_one(values: &[InputType]) -> crate::Result<Answer> {
+    Ok(Answer::from(product_of_sum(values, 2, SHITHOLE_YEAR)?))
+}
+
+/// The product of the three entries summing to [SHITHOLE_YEAR].
+pub fn part_two(values: &[InputType]) -> crate::Result<Answer> {
+    Ok(Answer::from(product_of_sum(values, 3, SHITHOLE_YEAR)?))
+}
+
+/// The product of `k` entries summing to `target`, see [find_sum].
+pub fn product_of_sum(values: &[InputType], k: usize, target: InputType) -> crate::Result<i64> {
+    let entries = match find_sum(values, k, target) {
+        Some(entries) => entries,
+        None => {
+            return Err(crate::Error::invariant(format!(
+                "could not find {} entries summing to {}",
+                k, target
+            )))
+        }
+    };
+    return product(&entries);
+}
+
+/// The product of `entries`, an error if it does not fit.
+pub fn product(entries: &[InputType]) -> crate::Result<i64> {
+    entries
+        .iter()
+        .try_fold(1i64, |product, &entry| product.checked_mul(entry as i64))
+        .ok_or_else(|| crate::Error::invariant("product of the entries overflows"))
+}
+
+/// Finds `k` entries, at distinct positions, summing to `target`, or None if there are none.
+///
+/// Returns the first combination [combinations] streams.
+pub fn find_sum(values: &[InputType], k: usize, target: InputType) -> Option<Vec<InputType>> {
+    let indices = combinations(values, k, target).next()?;
+    return Some(indices.iter().map(|&i| values[i]).collect());
+}
+
+/// Streams every combination of `k` distinct positions in `values` whose entries sum to
+/// `target`, as sorted indices in lexicographic order.
+///
+/// Equal entries at different positions make different combinations. The first `k - 1` positions
+/// are enumerated and the last looked up by value, so pairs take O(n) and every further entry
+/// multiplies that by n, plus the combinations found.
+pub fn combinations(values: &[InputType], k: usize, target: InputType) -> SumCombinations<'_> {
+    let mut positions: HashMap<InputType, Vec<usize>> = HashMap::new();
+    for (i, &value) in values.iter().enumerate() {
+        positions.entry(value).or_default().push(i);
+    }
+
+    let prefix = match k {
+        0 => None,
+        _ if k > values.len() => None,
+        _ => Some((0..k - 1).collect()),
+    };
+    return SumCombinations {
+        values,
+        target,
+        positions,
+        prefix,
+        current: Vec::new(),
+        pending: Vec::new(),
+        empty: k == 0 && target == 0,
+    };
+}
+
+/// Iterator returned by [combinations].
+pub struct SumCombinations<'a> {
+    values: &'a [InputType],
+    target: InputType,
+    /// Where each value is, in increasing order.
+    positions: HashMap<InputType, Vec<usize>>,
+    /// The next `k - 1` positions to complete, None once every prefix was tried.
+    prefix: Option<Vec<usize>>,
+    /// The prefix being completed and the last positions completing it, the next one last.
+    current: Vec<usize>,
+    pending: Vec<usize>,
+    /// Whether the empty combination is still to be yielded, it is the only one for k = 0.
+    empty: bool,
+}
+
+impl SumCombinations<'_> {
+    /// Moves `prefix` to the next `len` positions out of `n` in lexicographic order.
+    fn advance(prefix: &mut [usize], n: usize) -> bool {
+        // The last position of the combination still needs room after the prefix
+        let len = prefix.len();
+        for i in (0..len).rev() {
+            if prefix[i] < n - 1 - (len - i) {
+                prefix[i] += 1;
+                for j in i + 1..len {
+                    prefix[j] = prefix[j - 1] + 1;
+                }
+                return true;
+            }
+        }
+        return false;
+    }
+}
+
+impl Iterator for SumCombinations<'_> {
+    type Item = Vec<usize>;
+
+    fn next(&mut self) -> Option<Vec<usize>> {
+        if self.empty {
+            self.empty = false;
+            return Some(Vec::new());
+        }
+        loop {
+            if let Some(last) = self.pending.pop() {
+                let mut combination = self.current.clone();
+                combination.push(last);
+                return Some(combination);
+            }
+
+            let (values, positions) = (self.values, &self.positions);
+            let prefix = self.prefix.as_mut()?;
+            let sum: i64 = prefix.iter().map(|&i| values[i] as i64).sum();
+            let after = prefix.last().map_or(0, |&i| i + 1);
+            let completing = InputType::try_from(self.target as i64 - sum)
+                .ok()
+                .and_then(|wanted| positions.get(&wanted))
+                .map_or(&[][..], |positions| {
+                    &positions[positions.partition_point(|&i| i < after)..]
+                });
+            self.current = prefix.clone();
+            self.pending = completing.iter().rev().copied().collect();
+
+            if !Self::advance(prefix, values.len()) {
+                self.prefix = None;
+            }
+        }
+    }
+}
+
+/// How [summarize] reduces every combination summing to the target to one number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregate {
+    /// The product of the first combination's entries, the puzzle's answer.
+    Product,
+    /// The sum over all combinations of the product of their entries.
+    Sum,
+    /// The smallest product of any combination's entries.
+    MinProduct,
+    /// The largest product of any combination's entries.
+    MaxProduct,
+}
+
+/// The number of combinations summing to the target and their [Aggregate], None if there are
+/// none.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Summary {
+    pub count: u64,
+    pub value: Option<i64>,
+}
+
+/// Counts every combination of `k` entries summing to `target` and aggregates them with `how`.
+///
+/// Errors if the aggregate does not fit in an i64.
+pub fn summarize(
+    values: &[InputType],
+    k: usize,
+    target: InputType,
+    how: Aggregate,
+) -> crate::Result<Summary> {
+    let mut summary = Summary {
+        count: 0,
+        value: None,
+    };
+    for indices in combinations(values, k, target) {
+        summary.count += 1;
+        if how == Aggregate::Product && summary.value.is_some() {
+            continue;
+        }
+        let entries: Vec<InputType> = indices.iter().map(|&i| values[i]).collect();
+        let product = product(&entries)?;
+        summary.value = Some(match (how, summary.value) {
+            (_, None) | (Aggregate::Product, _) => product,
+            (Aggregate::Sum, Some(sum)) => sum
+                .checked_add(product)
+                .ok_or_else(|| crate::Error::invariant("sum of the products overflows"))?,
+            (Aggregate::MinProduct, Some(min)) => min.min(product),
+            (Aggregate::MaxProduct, Some(max)) => max.max(product),
+        });
+    }
+    return Ok(summary);
+}
+
+/// Which subset [subset_sum] picks when subsets of several sizes hit the target.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Prefer {
+    Fewest,
+    Most,
+}
+
+/// The most (entry count, sum) cells [subset_sum] will allocate, 4 bytes each.
+const MAX_SUBSET_CELLS: usize = 1 << 25;
+
+/// Finds a subset of between `min` and `max` entries summing to `target`, with the fewest or the
+/// most entries, returning the positions of its entries in increasing order.
+///
+/// Dynamic programming over (number of entries, sum), so it takes O(n * max * S) time for S the
+/// spread of sums that can still reach `target`, which is at most `target` for non-negative
+/// entries. Errors if there is no such subset or the table would be too large.
+pub fn subset_sum(
+    values: &[InputType],
+    target: InputType,
+    min: usize,
+    max: usize,
+    prefer: Prefer,
+) -> crate::Result<Vec<usize>> {
+    let not_found = || {
+        crate::Error::invariant(format!(
+            "no subset of {} to {} entries sums to {}",
+            min, max, target
+        ))
+    };
+    let max = max.min(values.len());
+    if min > max {
+        return Err(not_found());
+    }
+
+    // Partial sums outside [low, high] can not be completed to the target
+    let positive: i64 = values.iter().map(|&v| v.max(0) as i64).sum();
+    let negative: i64 = values.iter().map(|&v| v.min(0) as i64).sum();
+    let target = target as i64;
+    let low = negative.max(target - positive);
+    let high = positive.min(target - negative);
+    if low > 0 || high < 0 {
+        return Err(not_found());
+    }
+    let width = (high - low + 1) as usize;
+    if width.saturating_mul(max + 1) > MAX_SUBSET_CELLS {
+        return Err(crate::Error::validation(format!(
+            "entries are too spread out to search for subsets of up to {} entries",
+            max
+        )));
+    }
+
+    // reached[c][s - low] is 1 + the entry that first made a sum of s from c entries, 0 if none
+    const EMPTY: u32 = u32::MAX;
+    let mut reached = vec![vec![0u32; width]; max + 1];
+    reached[0][-low as usize] = EMPTY;
+    for (i, &value) in values.iter().enumerate() {
+        // Fewer entries first would let this entry extend sums it just made
+        for c in (0..max.min(i + 1)).rev() {
+            let (before, after) = reached.split_at_mut(c + 1);
+            let (from, to) = (&before[c], &mut after[0]);
+            for (offset, &marker) in from.iter().enumerate() {
+                let sum = offset as i64 + low + value as i64;
+                if marker == 0 || sum < low || sum > high {
+                    continue;
+                }
+                let cell = &mut to[(sum - low) as usize];
+                if *cell == 0 {
+                    *cell = i as u32 + 1;
+                }
+            }
+        }
+    }
+
+    let target_offset = (target - low) as usize;
+    let mut sizes = (min..=max).filter(|&c| reached[c][target_offset] != 0);
+    let size = match prefer {
+        Prefer::Fewest => sizes.next(),
+        Prefer::Most => sizes.next_back(),
+    };
+    let mut c = size.ok_or_else(not_found)?;
+
+    // Walk back through the entries that first made each sum
+    let mut chosen = Vec::with_capacity(c);
+    let mut sum = target;
+    while c > 0 {
+        let i = (reached[c][(sum - low) as usize] - 1) as usize;
+        chosen.push(i);
+        sum -= values[i] as i64;
+        c -= 1;
+    }
+    chosen.reverse();
+    return Ok(chosen);
+}
+
+/// Tries every combination of `k` entries in input order, the obviously correct [find_sum].
+pub fn reference_sum(values: &[InputType], k: usize, target: InputType) -> crate::Result<Answer> {
+    /// The entries of the first combination, last entry first.
+    fn search(values: &[InputType], k: usize, target: i64) -> Option<Vec<InputType>> {
+        if k == 0 {
+            return if target == 0 { Some(Vec::new()) } else { None };
+        }
+        for (i, &value) in values.iter().enumerate() {
+            if let Some(mut entries) = search(&values[i + 1..], k - 1, target - value as i64) {
+                entries.push(value);
+                return Some(entries);
+            }
+        }
+        return None;
+    }
+
+    match search(values, k, target as i64) {
+        Some(entries) => Ok(Answer::from(product(&entries)?)),
+        None => Err(crate::Error::invariant(format!(
+            "could not find {} entries summing to {}",
+            k, target
+        ))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests! {
+        Day1;
+        test_example_part_one: example 1, part One => 514579;
+        test_example_part_two: example 1, part Two => 241861950;
+    }
+
+    #[test]
+    fn test_find_sum_any_k() {
+        let values = [1721, 979, 366, 299, 675, 1456];
+        assert_eq!(find_sum(&values, 1, 366), Some(vec![366]));
+        assert_eq!(find_sum(&values, 2, 2020), Some(vec![1721, 299]));
+        assert_eq!(find_sum(&values, 3, 2020), Some(vec![979, 366, 675]));
+        assert_eq!(
+            find_sum(&values, 4, 1721 + 979 + 366 + 299),
+            Some(vec![1721, 979, 366, 299])
+        );
+        assert_eq!(find_sum(&values, 2, 5), None);
+        assert_eq!(find_sum(&values, 7, 2020), None);
+    }
+
+    #[test]
+    fn test_find_sum_uses_each_entry_once() {
+        assert_eq!(find_sum(&[1010], 2, 2020), None);
+        assert_eq!(find_sum(&[1010, 1010], 2, 2020), Some(vec![1010, 1010]));
+        assert_eq!(find_sum(&[-5, 10, 2025], 2, 2020), Some(vec![-5, 2025]));
+    }
+
+    #[test]
+    fn test_combinations_streams_every_solution_by_index() {
+        let values = [1, 2, 3, 2, 1];
+        let all: Vec<Vec<usize>> = combinations(&values, 2, 3).collect();
+        assert_eq!(all, vec![vec![0, 1], vec![0, 3], vec![1, 4], vec![3, 4]]);
+        let all: Vec<Vec<usize>> = combinations(&values, 3, 6).collect();
+        assert_eq!(
+            all,
+            vec![vec![0, 1, 2], vec![0, 2, 3], vec![1, 2, 4], vec![2, 3, 4]]
+        );
+        assert_eq!(combinations(&values, 0, 0).count(), 1);
+        assert_eq!(combinations(&values, 6, 9).count(), 0);
+        assert_eq!(combinations(&[], 1, 0).count(), 0);
+    }
+
+    #[test]
+    fn test_summarize_aggregates() {
+        let values = [1, 2, 3, 2, 1, 4];
+        let summary = |how| summarize(&values, 2, 5, how).unwrap();
+        assert_eq!(summary(Aggregate::Product).count, 4);
+        assert_eq!(summary(Aggregate::Product).value, Some(4));
+        assert_eq!(summary(Aggregate::Sum).value, Some(4 + 6 + 6 + 4));
+        assert_eq!(summary(Aggregate::MinProduct).value, Some(4));
+        assert_eq!(summary(Aggregate::MaxProduct).value, Some(6));
+        let none = summarize(&values, 2, 100, Aggregate::Product).unwrap();
+        assert_eq!((none.count, none.value), (0, None));
+    }
+
+    #[test]
+    fn test_reference_checks_overflow_like_the_solver() {
+        let values = [1 << 30, 1 << 30, -(1 << 30)];
+        let solver = product_of_sum(&values, 3, 1 << 30).unwrap_err();
+        let reference = reference_sum(&values, 3, 1 << 30).unwrap_err();
+        assert_eq!(solver.kind(), crate::ErrorKind::Invariant);
+        assert_eq!(reference.kind(), solver.kind());
+        assert_eq!(reference.to_string(), solver.to_string());
+
+        let values = [1721, 979, 366, 299, 675, 1456];
+        assert_eq!(
+            reference_sum(&values, 3, 2020).unwrap(),
+            Answer::from(product_of_sum(&values, 3, 2020).unwrap())
+        );
+    }
+
+    #[test]
+    fn test_summarize_checks_overflow() {
+        let values = [1 << 30, 1 << 30, -(1 << 30)];
+        let e = summarize(&values, 3, 1 << 30, Aggregate::Product).unwrap_err();
+        assert_eq!(e.kind(), crate::ErrorKind::Invariant);
+    }
+
+    #[test]
+    fn test_subset_sum_prefers_fewest_or_most_entries() {
+        let values = [1721, 979, 366, 299, 675, 1456];
+        let subset = |min, max, prefer| {
+            let indices = subset_sum(&values, 2020, min, max, prefer).unwrap();
+            indices.iter().map(|&i| values[i]).collect::<Vec<_>>()
+        };
+        assert_eq!(subset(0, 6, Prefer::Fewest), vec![1721, 299]);
+        assert_eq!(subset(0, 6, Prefer::Most), vec![979, 366, 675]);
+        assert_eq!(subset(3, 6, Prefer::Fewest), vec![979, 366, 675]);
+        assert!(subset_sum(&values, 2020, 4, 6, Prefer::Fewest).is_err());
+    }
+
+    #[test]
+    fn test_subset_sum_handles_negatives_and_duplicates() {
+        let values = [5, -3, 5, 8, -2];
+        let subset = |target, min, prefer| subset_sum(&values, target, min, 5, prefer).unwrap();
+        assert_eq!(subset(0, 1, Prefer::Fewest), vec![0, 1, 4]);
+        assert_eq!(subset(10, 0, Prefer::Fewest), vec![0, 2]);
+        assert_eq!(subset(13, 0, Prefer::Most), vec![0, 1, 2, 3, 4]);
+        assert_eq!(subset(0, 0, Prefer::Fewest), vec![]);
+    }
+
+    #[test]
+    fn test_subset_sum_errors_without_a_subset() {
+        let e = subset_sum(&[2, 4, 6], 5, 0, 3, Prefer::Fewest).unwrap_err();
+        assert_eq!(e.kind(), crate::ErrorKind::Invariant);
+        let e = subset_sum(&[2, 4, 6], 100, 0, 3, Prefer::Most).unwrap_err();
+        assert_eq!(e.kind(), crate::ErrorKind::Invariant);
+        assert!(subset_sum(&[], 0, 1, 3, Prefer::Most).is_err());
+    }
+
+    #[test]
+    fn test_product_of_sum_reports_missing_entries() {
+        let e = product_of_sum(&[1, 2, 3], 2, 2020).unwrap_err();
+        assert_eq!(e.kind(), crate::ErrorKind::Invariant);
+    }
+}
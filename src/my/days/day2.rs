@@ -0,0 +1,307 @@
+//! Day 2: Password Philosophy, count the passwords that satisfy their corporate policy.
+
+use crate::input::InputReader;
+use crate::solution::{Answer, Solution};
+use std::fmt;
+use std::str::FromStr;
+
+mod rule;
+
+pub use rule::Rule;
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Database;
+
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn parse(&self, reader: InputReader) -> crate::Result<Self::Input> {
+        parse(reader)
+    }
+
+    fn part_one(&self, input: &Self::Input) -> crate::Result<Answer> {
+        part_one(input)
+    }
+
+    fn part_two(&self, input: &Self::Input) -> crate::Result<Answer> {
+        part_two(input)
+    }
+}
+
+/// Reads one [Entry] per line.
+pub fn parse(reader: InputReader) -> crate::Result<Database> {
+    let source = String::from(reader.source());
+    let entries = reader
+        .parse_lines::<Entry>()
+        .collect::<crate::Result<_>>()?;
+    return Ok(Database { entries, source });
+}
+
+/// The number of passwords valid under the sled rental policy.
+pub fn part_one(database: &Database) -> crate::Result<Answer> {
+    return Ok(Answer::from(count_valid(
+        database,
+        Policy::SledRental,
+        Unit::Scalar,
+    )?));
+}
+
+/// The number of passwords valid under the Toboggan corporate policy.
+pub fn part_two(database: &Database) -> crate::Result<Answer> {
+    return Ok(Answer::from(count_valid(
+        database,
+        Policy::TobogganCorporate,
+        Unit::Scalar,
+    )?));
+}
+
+/// The number of entries whose password satisfies its rule under `policy`, counting positions
+/// in `unit`s.
+pub fn count_valid(database: &Database, policy: Policy, unit: Unit) -> crate::Result<usize> {
+    let mut count = 0;
+    for (index, entry) in database.entries.iter().enumerate() {
+        if entry
+            .test(policy, unit)
+            .map_err(|e| database.locate(e, index))?
+        {
+            count += 1;
+        }
+    }
+    return Ok(count);
+}
+
+/// The number of valid passwords under each of `policies`, in one pass over the database.
+///
+/// Each policy has its own result, so a rule one policy can not test, e.g. a position past the end
+/// of the password, does not fail the others.
+pub fn count_all(
+    database: &Database,
+    policies: &[Policy],
+    unit: Unit,
+) -> Vec<(Policy, crate::Result<usize>)> {
+    let mut counts: Vec<(Policy, crate::Result<usize>)> =
+        policies.iter().map(|&policy| (policy, Ok(0))).collect();
+    for (index, entry) in database.entries.iter().enumerate() {
+        for (policy, count) in counts.iter_mut() {
+            if let Ok(valid) = count {
+                match entry.test(*policy, unit) {
+                    Ok(true) => *valid += 1,
+                    Ok(false) => (),
+                    Err(e) => *count = Err(database.locate(e, index)),
+                }
+            }
+        }
+    }
+    return counts;
+}
+
+/// The password database, its entries and where they were read from.
+pub struct Database {
+    entries: Vec<Entry>,
+    source: String,
+}
+
+impl Database {
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Where the database was read from, see [InputReader::source].
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// Points `e` at the line of the entry at `index`.
+    fn locate(&self, e: crate::Error, index: usize) -> crate::Error {
+        let entry = &self.entries[index];
+        e.with_line(index + 1, &entry.to_string())
+            .with_file(&self.source)
+    }
+}
+
+/// What a password's positions and occurrences are counted in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Unit {
+    /// Unicode scalar values, i.e. `char`s.
+    Scalar,
+    /// Grapheme clusters, what a reader sees as one character, e.g. `e` and a combining accent.
+    Grapheme,
+}
+
+/// How a [Rule]'s numbers are interpreted.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// The character occurs between the low and the high number of times, inclusive.
+    SledRental,
+    /// The character is at exactly one of the 1-based positions.
+    TobogganCorporate,
+}
+
+impl Policy {
+    pub const ALL: [Policy; 2] = [Policy::SledRental, Policy::TobogganCorporate];
+
+    /// The name used on the command line.
+    pub fn name(self) -> &'static str {
+        match self {
+            Policy::SledRental => "count",
+            Policy::TobogganCorporate => "position",
+        }
+    }
+
+    /// Parses a command line selection of policies, a [Policy::name] or `both`.
+    pub fn selection(name: &str) -> crate::Result<Vec<Policy>> {
+        match name {
+            "both" => return Ok(Policy::ALL.to_vec()),
+            _ => return Ok(vec![name.parse()?]),
+        }
+    }
+}
+
+impl FromStr for Policy {
+    type Err = crate::Error;
+
+    /// Parses a [Policy::name].
+    fn from_str(name: &str) -> crate::Result<Policy> {
+        match Policy::ALL.iter().find(|policy| policy.name() == name) {
+            Some(policy) => Ok(*policy),
+            None => Err(crate::Error::parse(format!(
+                "unknown policy '{}', expected 'count' or 'position'",
+                name
+            ))),
+        }
+    }
+}
+
+/// A line of the password database, a [Rule] and the password it applies to.
+pub struct Entry {
+    rule: Rule,
+    password: String,
+}
+
+impl Entry {
+    pub fn rule(&self) -> &Rule {
+        &self.rule
+    }
+
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+
+    /// Whether the password satisfies the rule, see [Rule::test].
+    pub fn test(&self, policy: Policy, unit: Unit) -> crate::Result<bool> {
+        self.rule.test(&self.password, policy, unit)
+    }
+}
+
+impl fmt::Display for Entry {
+    /// Formats the entry back into its database line.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.rule, self.password)
+    }
+}
+
+impl FromStr for Entry {
+    type Err = crate::Error;
+
+    fn from_str(line: &str) -> crate::Result<Self> {
+        let (rule_string, password) = match line.split_once(": ") {
+            Some(parts) => parts,
+            None => return Err(crate::Error::parse("expected '<rule>: <password>'")),
+        };
+
+        return Ok(Entry {
+            rule: rule_string.parse()?,
+            password: String::from(password),
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn database(lines: &[&str]) -> Database {
+        let text = lines.iter().map(|line| format!("{}\n", line)).collect();
+        parse(InputReader::from_text(text, String::from("passwords"))).unwrap()
+    }
+
+    crate::example_tests! {
+        Day2;
+        test_example_part_one: example 1, part One => 2u64;
+        test_example_part_two: example 1, part Two => 1u64;
+    }
+
+    #[test]
+    fn test_policies() {
+        let rule: Rule = "1-3 a".parse().unwrap();
+        let test = |password, policy| rule.test(password, policy, Unit::Scalar).unwrap();
+        assert!(test("abcde", Policy::SledRental));
+        assert!(test("abcde", Policy::TobogganCorporate));
+        assert!(!test("abbbaaa", Policy::SledRental));
+        assert!(test("abbbaaa", Policy::TobogganCorporate));
+        assert!(test("abade", Policy::SledRental));
+        assert!(!test("abade", Policy::TobogganCorporate));
+        assert!(!test("aaaa", Policy::SledRental));
+        assert!(!test("aaaa", Policy::TobogganCorporate));
+    }
+
+    #[test]
+    fn test_invalid_positions() {
+        let zero: Rule = "0-2 a".parse().unwrap();
+        let e = zero
+            .test("abc", Policy::TobogganCorporate, Unit::Scalar)
+            .unwrap_err();
+        assert_eq!(e.kind(), crate::ErrorKind::Validation);
+        // Zero occurrences are fine by count
+        assert!(zero.test("bbb", Policy::SledRental, Unit::Scalar).unwrap());
+
+        let entries = database(&["1-3 a: abcde", "1-4 é: éé"]);
+        let e = count_valid(&entries, Policy::TobogganCorporate, Unit::Scalar).unwrap_err();
+        assert_eq!(e.kind(), crate::ErrorKind::Validation);
+        assert!(e.to_string().contains("1-4 é: éé"));
+        let location = e.location().unwrap();
+        assert_eq!(
+            (location.file.as_deref(), location.line),
+            (Some("passwords"), 2)
+        );
+
+        let counts = count_all(&entries, &Policy::ALL, Unit::Scalar);
+        let e = counts[1].1.as_ref().unwrap_err();
+        assert_eq!(e.location().unwrap().file.as_deref(), Some("passwords"));
+    }
+
+    #[test]
+    fn test_count_all() {
+        let entries = database(&["1-3 a: abcde", "1-3 b: cdefg", "2-9 c: ccccccccc"]);
+        let counts = count_all(&entries, &Policy::ALL, Unit::Scalar);
+        assert_eq!(counts.len(), 2);
+        for ((policy, count), expected) in counts.into_iter().zip([2, 1].iter()) {
+            assert_eq!(count.unwrap(), *expected);
+            assert_eq!(
+                count_valid(&entries, policy, Unit::Scalar).unwrap(),
+                *expected
+            );
+            assert_eq!(policy.name().parse::<Policy>().unwrap(), policy);
+        }
+    }
+
+    #[test]
+    fn test_count_all_only_tests_the_selected_policies() {
+        // Position 3 is past the end, which only the position policy cares about
+        let entries = database(&["1-3 a: ab"]);
+        let count_only = Policy::selection("count").unwrap();
+        let counts = count_all(&entries, &count_only, Unit::Scalar);
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts[0].0, Policy::SledRental);
+        assert_eq!(counts[0].1.as_ref().unwrap(), &1);
+
+        let counts = count_all(&entries, &Policy::selection("both").unwrap(), Unit::Scalar);
+        assert_eq!(counts[0].1.as_ref().unwrap(), &1);
+        let e = counts[1].1.as_ref().unwrap_err();
+        assert_eq!(e.kind(), crate::ErrorKind::Validation);
+        assert!(Policy::selection("neither").is_err());
+    }
+}
@@ -0,0 +1,626 @@
+//! Password rules, the puzzle's `1-3 a` extended into a small policy language:
+//!
+//! ```text
+//! rule    = either { "or" either }
+//! either  = all { "xor" all }
+//! all     = unary { "and" unary }
+//! unary   = "not" unary | "(" rule ")" | term
+//! term    = number { "-" number } class         e.g. `1-3 a`, read by the Policy
+//!         | "count" number [ "-" number ] class  occurrences of the class, inclusive
+//!         | "at" number class                    the 1-based position holds the class
+//! class   = character | "[" [ "^" ] { item } "]" | name
+//! item    = character [ "-" character ]          `\` escapes `]`, `-`, `^` and `\`
+//! name    = "any" | "alnum" | "digit" | "letter" | "lower" | "upper" | "punct" | "space"
+//! ```
+//!
+//! So `1-3 a` is still the puzzle's rule, and `at 1 a xor at 3 a` or `count 1-3 a` spell out one
+//! of its policies. Its numbers must not decrease. The count policy reads one number as an exact
+//! count and two as a range, and can not read more. The position policy reads any number of
+//! positions. `not` binds tightest, then `and`, `xor` and `or`.
+
+use super::{Policy, Unit};
+use std::fmt;
+use std::str::FromStr;
+
+/// A password policy, e.g. `1-3 a` or `count 2-5 [a-f] and not at 1 digit`.
+pub struct Rule {
+    text: String,
+    expr: Expr,
+}
+
+enum Expr {
+    /// The puzzle's rule, what the numbers mean depends on the [Policy].
+    Numbers {
+        numbers: Vec<usize>,
+        class: Class,
+    },
+    Count {
+        low: usize,
+        high: usize,
+        class: Class,
+    },
+    At {
+        position: usize,
+        class: Class,
+    },
+    Not(Box<Expr>),
+    Binary(Op, Box<Expr>, Box<Expr>),
+}
+
+#[derive(Clone, Copy)]
+enum Op {
+    And,
+    Xor,
+    Or,
+}
+
+/// Operators from the loosest binding to the tightest.
+const OPERATORS: [(&str, Op); 3] = [("or", Op::Or), ("xor", Op::Xor), ("and", Op::And)];
+
+/// What a unit of the password is matched against.
+enum Class {
+    Character(char),
+    Set {
+        negated: bool,
+        ranges: Vec<(char, char)>,
+    },
+    Named(Matcher),
+}
+
+/// Whether a character is in a named class.
+type Matcher = fn(char) -> bool;
+
+const NAMES: [(&str, Matcher); 8] = [
+    ("any", |_| true),
+    ("alnum", char::is_alphanumeric),
+    ("digit", |c| c.is_ascii_digit()),
+    ("letter", char::is_alphabetic),
+    ("lower", char::is_lowercase),
+    ("upper", char::is_uppercase),
+    ("punct", |c| c.is_ascii_punctuation()),
+    ("space", char::is_whitespace),
+];
+
+impl Rule {
+    /// The character and numbers of a rule in the puzzle's own `1-3 a` form.
+    pub fn simple(&self) -> Option<(char, &[usize])> {
+        match &self.expr {
+            Expr::Numbers {
+                numbers,
+                class: Class::Character(character),
+            } => Some((*character, numbers)),
+            _ => None,
+        }
+    }
+
+    /// Whether `password` satisfies the rule, with positions and occurrences counted in `unit`s
+    /// and `1-3 a` terms read by `policy`.
+    ///
+    /// Errors with [Validation](crate::ErrorKind::Validation) if a position is 0 or past the end
+    /// of the password. Both sides of an operator are always tested, so such a position is an
+    /// error whatever the other side is.
+    pub fn test(&self, password: &str, policy: Policy, unit: Unit) -> crate::Result<bool> {
+        return self.expr.test(&split(password, unit), policy);
+    }
+}
+
+impl Expr {
+    fn test(&self, units: &[&str], policy: Policy) -> crate::Result<bool> {
+        match self {
+            Expr::Numbers { numbers, class } => match policy {
+                Policy::SledRental => {
+                    let (low, high) = match numbers.as_slice() {
+                        [exactly] => (*exactly, *exactly),
+                        [low, high] => (*low, *high),
+                        _ => {
+                            return Err(crate::Error::validation(format!(
+                                "a count takes one or two numbers, not {}",
+                                numbers.len()
+                            )))
+                        }
+                    };
+                    return Ok((low..=high).contains(&count(units, class)));
+                }
+                Policy::TobogganCorporate => {
+                    let mut matches = 0;
+                    for &position in numbers {
+                        matches += at(units, position, class)? as u32;
+                    }
+                    return Ok(matches == 1);
+                }
+            },
+            Expr::Count { low, high, class } => {
+                return Ok((*low..=*high).contains(&count(units, class)));
+            }
+            Expr::At { position, class } => return at(units, *position, class),
+            Expr::Not(expr) => return Ok(!expr.test(units, policy)?),
+            Expr::Binary(op, left, right) => {
+                let (left, right) = (left.test(units, policy)?, right.test(units, policy)?);
+                return Ok(match op {
+                    Op::And => left && right,
+                    Op::Xor => left != right,
+                    Op::Or => left || right,
+                });
+            }
+        }
+    }
+}
+
+/// How many of `units` are in `class`.
+fn count(units: &[&str], class: &Class) -> usize {
+    units.iter().filter(|unit| class.matches(unit)).count()
+}
+
+/// Whether the unit at the 1-based `position` is in `class`.
+fn at(units: &[&str], position: usize, class: &Class) -> crate::Result<bool> {
+    let unit = match position {
+        0 => return Err(crate::Error::validation("positions start at 1, not 0")),
+        _ => units.get(position - 1),
+    };
+    match unit {
+        Some(unit) => return Ok(class.matches(unit)),
+        None => {
+            return Err(crate::Error::validation(format!(
+                "position {} is past the end of a {} character password",
+                position,
+                units.len()
+            )))
+        }
+    }
+}
+
+impl Class {
+    /// Whether `unit` is in the class. A character only matches a unit that is just that
+    /// character, sets and names look at the unit's first character, e.g. the `e` of `é` spelled
+    /// with a combining accent.
+    fn matches(&self, unit: &str) -> bool {
+        let mut chars = unit.chars();
+        let first = match chars.next() {
+            Some(first) => first,
+            None => return false,
+        };
+        match self {
+            Class::Character(character) => *character == first && chars.next().is_none(),
+            Class::Set { negated, ranges } => {
+                let contained = ranges
+                    .iter()
+                    .any(|&(low, high)| low <= first && first <= high);
+                contained != *negated
+            }
+            Class::Named(matches) => matches(first),
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    /// Formats the rule the way it was written, e.g. `1-3 a`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.text)
+    }
+}
+
+impl FromStr for Rule {
+    type Err = crate::Error;
+
+    /// Parses a rule in the language described in the [module](self) documentation. Errors point
+    /// at the 1-based column of the problem, counted in characters.
+    fn from_str(rule_string: &str) -> crate::Result<Rule> {
+        let mut parser = Parser {
+            chars: rule_string.chars().collect(),
+            at: 0,
+        };
+        let expr = parser.expression(0)?;
+        parser.skip_space();
+        if let Some(c) = parser.peek() {
+            return Err(parser.error(format!("unexpected '{}' after the rule", c)));
+        }
+
+        return Ok(Rule {
+            text: String::from(rule_string.trim()),
+            expr,
+        });
+    }
+}
+
+/// A recursive descent parser over a rule's characters.
+struct Parser {
+    chars: Vec<char>,
+    /// Index of the next character, one less than its column.
+    at: usize,
+}
+
+impl Parser {
+    /// Parses the operators from `OPERATORS[level]` on.
+    fn expression(&mut self, level: usize) -> crate::Result<Expr> {
+        let (word, op) = match OPERATORS.get(level) {
+            Some(&operator) => operator,
+            None => return self.unary(),
+        };
+        let mut expr = self.expression(level + 1)?;
+        while self.keyword(word) {
+            let right = self.expression(level + 1)?;
+            expr = Expr::Binary(op, Box::new(expr), Box::new(right));
+        }
+        return Ok(expr);
+    }
+
+    fn unary(&mut self) -> crate::Result<Expr> {
+        self.skip_space();
+        if self.keyword("not") {
+            return Ok(Expr::Not(Box::new(self.unary()?)));
+        }
+        if self.peek() == Some('(') {
+            self.at += 1;
+            let expr = self.expression(0)?;
+            self.skip_space();
+            if self.peek() != Some(')') {
+                return Err(self.error("expected ')'"));
+            }
+            self.at += 1;
+            return Ok(expr);
+        }
+
+        if self.keyword("count") {
+            self.skip_space();
+            let start = self.at;
+            let low = self.number()?;
+            let high = match self.peek() {
+                Some('-') => {
+                    self.at += 1;
+                    self.number()?
+                }
+                _ => low,
+            };
+            if low > high {
+                return Err(crate::Error::parse("count range is empty").with_column(start + 1));
+            }
+            let class = self.class()?;
+            return Ok(Expr::Count { low, high, class });
+        }
+        if self.keyword("at") {
+            self.skip_space();
+            let start = self.at;
+            let position = self.number()?;
+            if position == 0 {
+                return Err(crate::Error::parse("positions start at 1").with_column(start + 1));
+            }
+            let class = self.class()?;
+            return Ok(Expr::At { position, class });
+        }
+
+        match self.peek() {
+            Some(c) if c.is_ascii_digit() => {
+                let start = self.at;
+                let mut numbers = vec![self.number()?];
+                while self.peek() == Some('-') {
+                    self.at += 1;
+                    numbers.push(self.number()?);
+                }
+                // Read as a count the numbers are a range, read as positions they are a set
+                if numbers.windows(2).any(|pair| pair[0] > pair[1]) {
+                    let message = match numbers.len() {
+                        2 => "range is empty",
+                        _ => "positions must be in increasing order",
+                    };
+                    return Err(crate::Error::parse(message).with_column(start + 1));
+                }
+                let class = self.class()?;
+                return Ok(Expr::Numbers { numbers, class });
+            }
+            Some(_) => return Err(self.error("expected a rule, e.g. '1-3 a'")),
+            None => return Err(self.error("expected a rule")),
+        }
+    }
+
+    fn number(&mut self) -> crate::Result<usize> {
+        let start = self.at;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.at += 1;
+        }
+        if start == self.at {
+            return Err(self.error("expected a number"));
+        }
+        let digits: String = self.chars[start..self.at].iter().collect();
+        return digits.parse().map_err(|e| {
+            crate::Error::parse("invalid rule number")
+                .with_source(e)
+                .with_column(start + 1)
+        });
+    }
+
+    /// Parses the whitespace and class that end a term.
+    fn class(&mut self) -> crate::Result<Class> {
+        let before = self.at;
+        self.skip_space();
+        let c = match self.peek() {
+            Some(c) if self.at > before => c,
+            Some(_) => return Err(self.error("expected a space before the character")),
+            None => return Err(self.error("rule is missing its character")),
+        };
+
+        let start = self.at;
+        if self.ends_token(start + 1) {
+            self.at += 1;
+            return Ok(Class::Character(c));
+        }
+        let class = match c {
+            '[' => self.set()?,
+            c if c.is_alphabetic() => {
+                while self.peek().is_some_and(char::is_alphanumeric) {
+                    self.at += 1;
+                }
+                let name: String = self.chars[start..self.at].iter().collect();
+                match NAMES.iter().find(|(known, _)| *known == name) {
+                    Some(&(_, matches)) => Class::Named(matches),
+                    None => {
+                        return Err(crate::Error::parse(format!("unknown class '{}'", name))
+                            .with_column(start + 1))
+                    }
+                }
+            }
+            _ => return Err(self.error("expected a character, a [set] or a class name")),
+        };
+        if !self.ends_token(self.at) {
+            return Err(self.error("expected a space or ')' after the class"));
+        }
+        return Ok(class);
+    }
+
+    /// Parses a `[...]` set, the next character is its `[`.
+    fn set(&mut self) -> crate::Result<Class> {
+        let open = self.at;
+        self.at += 1;
+        let negated = self.peek() == Some('^');
+        if negated {
+            self.at += 1;
+        }
+
+        let mut ranges = Vec::new();
+        while let Some(low) = self.set_item(open)? {
+            let mut high = low;
+            if self.peek() == Some('-') && self.chars.get(self.at + 1) != Some(&']') {
+                self.at += 1;
+                let start = self.at;
+                high = match self.set_item(open)? {
+                    Some(high) if high >= low => high,
+                    _ => return Err(crate::Error::parse("empty range").with_column(start + 1)),
+                };
+            }
+            ranges.push((low, high));
+        }
+        if ranges.is_empty() {
+            return Err(crate::Error::parse("empty set").with_column(open + 1));
+        }
+        return Ok(Class::Set { negated, ranges });
+    }
+
+    /// The next character of the set opened at `open`, or None at its `]`.
+    fn set_item(&mut self, open: usize) -> crate::Result<Option<char>> {
+        let unterminated = || crate::Error::parse("set is missing its ']'").with_column(open + 1);
+        let c = match self.peek() {
+            Some(']') => {
+                self.at += 1;
+                return Ok(None);
+            }
+            Some('\\') => {
+                self.at += 1;
+                self.peek().ok_or_else(unterminated)?
+            }
+            Some(c) => c,
+            None => return Err(unterminated()),
+        };
+        self.at += 1;
+        return Ok(Some(c));
+    }
+
+    /// Consumes `word` if it comes next as a whole word.
+    fn keyword(&mut self, word: &str) -> bool {
+        self.skip_space();
+        let end = self.at + word.chars().count();
+        let matches = end <= self.chars.len()
+            && self.chars[self.at..end].iter().copied().eq(word.chars())
+            && !self.chars.get(end).is_some_and(|c| c.is_alphanumeric());
+        if matches {
+            self.at = end;
+        }
+        return matches;
+    }
+
+    /// Whether a token ends before the character at `index`.
+    fn ends_token(&self, index: usize) -> bool {
+        match self.chars.get(index) {
+            Some(&c) => c.is_whitespace() || c == ')',
+            None => true,
+        }
+    }
+
+    fn skip_space(&mut self) {
+        while self.peek().is_some_and(char::is_whitespace) {
+            self.at += 1;
+        }
+    }
+
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.at).copied()
+    }
+
+    fn error<S: Into<String>>(&self, message: S) -> crate::Error {
+        crate::Error::parse(message).with_column(self.at + 1)
+    }
+}
+
+/// Splits `password` into its `unit`s.
+fn split(password: &str, unit: Unit) -> Vec<&str> {
+    let mut units = Vec::new();
+    let mut start = 0;
+    let mut previous: Option<char> = None;
+    for (index, character) in password.char_indices() {
+        let joins = match (unit, previous) {
+            (Unit::Grapheme, Some(previous)) => extends(previous, character),
+            _ => false,
+        };
+        if index > 0 && !joins {
+            units.push(&password[start..index]);
+            start = index;
+        }
+        // A flag is a pair of regional indicators, a third one starts the next flag
+        previous = match (previous, character) {
+            (Some(p), c) if is_regional_indicator(p) && is_regional_indicator(c) && joins => None,
+            _ => Some(character),
+        };
+    }
+    if !password.is_empty() {
+        units.push(&password[start..]);
+    }
+    return units;
+}
+
+/// Whether `next` belongs to the same grapheme cluster as `previous`.
+///
+/// A std-only approximation of the extended grapheme cluster rules: combining marks, variation
+/// selectors, emoji modifiers and tags attach to what comes before, a zero width joiner glues the
+/// characters around it, regional indicators pair up into flags and CR LF is one cluster.
+fn extends(previous: char, next: char) -> bool {
+    const ZERO_WIDTH_JOINER: char = '\u{200D}';
+    return match (previous, next) {
+        ('\r', '\n') => true,
+        (ZERO_WIDTH_JOINER, _) => true,
+        (_, ZERO_WIDTH_JOINER) => true,
+        (p, n) if is_regional_indicator(p) && is_regional_indicator(n) => true,
+        (_, n) => is_extender(n),
+    };
+}
+
+fn is_extender(c: char) -> bool {
+    matches!(c,
+        '\u{0300}'..='\u{036F}'   // Combining diacritical marks
+        | '\u{0483}'..='\u{0489}' // Cyrillic
+        | '\u{0591}'..='\u{05BD}' // Hebrew points
+        | '\u{064B}'..='\u{065F}' // Arabic
+        | '\u{0900}'..='\u{0903}' // Devanagari signs
+        | '\u{093A}'..='\u{093C}'
+        | '\u{093E}'..='\u{094F}'
+        | '\u{1AB0}'..='\u{1AFF}' // Combining diacritical marks extended
+        | '\u{1DC0}'..='\u{1DFF}' // Combining diacritical marks supplement
+        | '\u{20D0}'..='\u{20FF}' // Combining marks for symbols
+        | '\u{3099}'..='\u{309A}' // Kana voicing marks
+        | '\u{FE00}'..='\u{FE0F}' // Variation selectors
+        | '\u{FE20}'..='\u{FE2F}' // Combining half marks
+        | '\u{1F3FB}'..='\u{1F3FF}' // Emoji skin tone modifiers
+        | '\u{E0020}'..='\u{E007F}' // Tags
+        | '\u{E0100}'..='\u{E01EF}' // Variation selectors supplement
+    )
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_unicode_positions() {
+        // Multibyte characters before the position must count as one each
+        let rule: Rule = "2-4 é".parse().unwrap();
+        assert_eq!(rule.simple().unwrap().0, 'é');
+        let test = |password, unit| rule.test(password, Policy::TobogganCorporate, unit);
+        assert!(test("ñéxx", Unit::Scalar).unwrap());
+        assert!(!test("ñéxé", Unit::Scalar).unwrap());
+
+        // e and a combining acute accent are two scalars but one grapheme
+        let rule: Rule = "2-4 x".parse().unwrap();
+        let test = |password, unit| rule.test(password, Policy::TobogganCorporate, unit);
+        assert!(!test("e\u{301}xyz", Unit::Scalar).unwrap());
+        assert!(test("e\u{301}xyz", Unit::Grapheme).unwrap());
+        assert_eq!(split("🇫🇷🇩🇪a", Unit::Grapheme), ["🇫🇷", "🇩🇪", "a"]);
+        assert_eq!(split("👩\u{200D}🔬👍🏽", Unit::Grapheme).len(), 2);
+        assert_eq!(split("", Unit::Grapheme).len(), 0);
+    }
+
+    #[test]
+    fn test_rule_columns_count_characters() {
+        let column = |rule: &str| {
+            rule.parse::<Rule>()
+                .err()
+                .unwrap()
+                .location()
+                .unwrap()
+                .column
+        };
+        assert_eq!(column("ü-3 a"), Some(1));
+        assert_eq!(column("1-é a"), Some(3));
+        assert_eq!(column("ñ"), Some(1));
+        assert_eq!(column("1-3"), Some(4));
+    }
+
+    #[test]
+    fn test_policy_language() {
+        let test = |rule: &str, password| {
+            let rule: Rule = rule.parse().unwrap();
+            rule.test(password, Policy::SledRental, Unit::Scalar)
+                .unwrap()
+        };
+        assert!(test("at 1 a xor at 3 a", "abcde"));
+        assert!(!test("at 1 a xor at 3 a", "abade"));
+        assert!(test("count 2 [a-c] and not at 5 digit", "xaxbx"));
+        assert!(!test("count 2 [a-c] and not at 5 digit", "xaxb1"));
+        assert!(test("not count 1-9 [^a-z] or at 1 upper", "Ab1"));
+        assert!(!test("not (count 1-9 [^a-z] or at 1 upper)", "Ab1"));
+        // and binds tighter than or
+        assert!(test("at 1 a or at 1 b and at 2 c", "ax"));
+        assert!(test("1-2 ) and at 1 (", "()"));
+        assert!(test("count 1 [\\]\\-] and 2-3 any", "a]b"));
+        assert!(test("at 2 ü or count 0 letter", "üü"));
+
+        // The puzzle's rules still depend on the policy
+        let rule: Rule = "2 a".parse().unwrap();
+        assert!(rule.test("aba", Policy::SledRental, Unit::Scalar).unwrap());
+        assert!(!rule.test("abaa", Policy::SledRental, Unit::Scalar).unwrap());
+        let rule: Rule = "1-2-3 a".parse().unwrap();
+        assert!(rule
+            .test("abb", Policy::TobogganCorporate, Unit::Scalar)
+            .unwrap());
+        let e = rule
+            .test("abb", Policy::SledRental, Unit::Scalar)
+            .unwrap_err();
+        assert_eq!(e.kind(), crate::ErrorKind::Validation);
+        let rule: Rule = "1-3 a and at 2 b".parse().unwrap();
+        assert!(rule.test("aba", Policy::SledRental, Unit::Scalar).unwrap());
+        assert!(!rule
+            .test("aba", Policy::TobogganCorporate, Unit::Scalar)
+            .unwrap());
+        assert!("1-3 a".parse::<Rule>().unwrap().simple().is_some());
+        assert!("count 1-3 a".parse::<Rule>().unwrap().simple().is_none());
+        assert_eq!(
+            " 1-3 a or at 2 [xy] ".parse::<Rule>().unwrap().to_string(),
+            "1-3 a or at 2 [xy]"
+        );
+    }
+
+    #[test]
+    fn test_policy_language_errors() {
+        let column = |rule: &str| {
+            let e = rule.parse::<Rule>().err().unwrap();
+            assert_eq!(e.kind(), crate::ErrorKind::Parse);
+            return e.location().unwrap().column;
+        };
+        assert_eq!(column("1-3 a nand at 1 b"), Some(7));
+        assert_eq!(column("count 3-1 a"), Some(7));
+        assert_eq!(column("at 0 a"), Some(4));
+        assert_eq!(column("at 1 vowel"), Some(6));
+        assert_eq!(column("at 1 [a-"), Some(6));
+        assert_eq!(column("at 1 [z-a]"), Some(9));
+        assert_eq!(column("at 1 []"), Some(6));
+        assert_eq!(column("(at 1 a or at 2 b"), Some(18));
+        assert_eq!(column("at 1 a and"), Some(11));
+        assert_eq!(column("not"), Some(4));
+        assert_eq!(column("1-3a"), Some(4));
+        assert_eq!(column("3-1 a"), Some(1));
+        assert_eq!(column("at 1 a or 2-5-4 b"), Some(11));
+
+        let e = "at 1 not".parse::<Rule>().err().unwrap();
+        assert!(e.to_string().ends_with("--> <input>, column 6"), "{}", e);
+    }
+}
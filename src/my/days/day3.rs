@@ -0,0 +1,237 @@
+//! Day 3: Toboggan Trajectory, count the trees struck sliding down a repeating map.
+
+use crate::input::InputReader;
+use crate::solution::{Answer, Solution};
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = TobogganMap;
+
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn parse(&self, reader: InputReader) -> crate::Result<Self::Input> {
+        parse(reader)
+    }
+
+    fn part_one(&self, map: &Self::Input) -> crate::Result<Answer> {
+        part_one(map)
+    }
+
+    fn part_two(&self, map: &Self::Input) -> crate::Result<Answer> {
+        part_two(map)
+    }
+}
+
+/// Reads the map, one row of `.` (open snow) and `#` (tree) per line.
+pub fn parse(reader: InputReader) -> crate::Result<TobogganMap> {
+    // Build the map
+    let mut map_details: Vec<Vec<Landmark>> = Vec::new();
+    let mut lines = reader.lines();
+    while let Some(line) = lines.next() {
+        let line = line?;
+        let locate = |e: crate::Error| {
+            e.with_line(lines.line_number(), &line)
+                .with_file(lines.source())
+        };
+
+        let mut map_row: Vec<Landmark> = Vec::new();
+        for (i, character) in line.chars().enumerate() {
+            match character {
+                '.' => map_row.push(Landmark::FreshPow),
+                '#' => map_row.push(Landmark::Tree),
+                _ => {
+                    let e = crate::Error::parse(format!("unknown landmark {:?}", character));
+                    return Err(locate(e.with_column(i + 1)));
+                }
+            }
+        }
+
+        // The map repeats sideways, so every row must be the same, non-zero, width
+        if map_row.is_empty() {
+            return Err(locate(crate::Error::validation("empty map row")));
+        }
+        if let Some(first_row) = map_details.first() {
+            if first_row.len() != map_row.len() {
+                let e = crate::Error::validation(format!(
+                    "map row is {} wide, expected {}",
+                    map_row.len(),
+                    first_row.len()
+                ));
+                return Err(locate(e));
+            }
+        }
+        map_details.push(map_row);
+    }
+
+    return Ok(TobogganMap::new(map_details));
+}
+
+/// The number of trees hit sliding right 3, down 1.
+pub fn part_one(map: &TobogganMap) -> crate::Result<Answer> {
+    let toboggan = Toboggan::new(1, 3);
+    return Ok(Answer::from(count_tree_strikes(map, &toboggan)?));
+}
+
+/// The product of the trees hit on each of the five surveyed slopes.
+pub fn part_two(map: &TobogganMap) -> crate::Result<Answer> {
+    let toboggans = [
+        Toboggan::new(1, 1),
+        Toboggan::new(1, 3),
+        Toboggan::new(1, 5),
+        Toboggan::new(1, 7),
+        Toboggan::new(2, 1),
+    ];
+
+    // Follow the route, a slope without trees makes the product zero
+    let mut strange_collision_product: usize = 1;
+    for toboggan in toboggans.iter() {
+        strange_collision_product = strange_collision_product
+            .checked_mul(count_tree_strikes(map, toboggan)?)
+            .ok_or_else(|| crate::Error::invariant("product of the tree counts overflows"))?;
+    }
+
+    return Ok(Answer::from(strange_collision_product));
+}
+
+/// Counts the trees `toboggan` hits on its way to the bottom of `map`.
+pub fn count_tree_strikes(map: &TobogganMap, toboggan: &Toboggan) -> crate::Result<usize> {
+    let mut num_collisions = 0;
+
+    for location in toboggan.slide() {
+        // Check if we have reached the bottom of the slope
+        if location.y as usize >= map.height() {
+            break;
+        }
+
+        match map.get(location) {
+            Some(Landmark::Tree) => num_collisions += 1,
+            Some(Landmark::FreshPow) => (),
+            None => {
+                return Err(crate::Error::invariant(format!(
+                    "here be dragons! {:?} is uncharted territory",
+                    location
+                )))
+            }
+        }
+    }
+
+    return Ok(num_collisions);
+}
+
+/// A location within a [TobogganMap].
+///
+/// [TobogganMap] is zeroed at the upper-left side of the slope so coordinates can be unsigned.
+#[derive(Clone, Copy, Debug)]
+pub struct Coordinate {
+    pub x: u32,
+    pub y: u32,
+}
+
+/// Points of interest within a [TobogganMap].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Landmark {
+    /// Map location containing fresh powder, a dream for all tobogganers.
+    FreshPow,
+    /// Map location containing a dangerous tree.
+    Tree,
+}
+
+/// Map for brave tobogganers to navigate themselves down a scary slope.
+pub struct TobogganMap {
+    // Row-major matrix of landmarks.
+    details: Vec<Vec<Landmark>>,
+    height: usize,
+}
+
+impl TobogganMap {
+    fn new(details: Vec<Vec<Landmark>>) -> Self {
+        let height = details.len();
+        Self { details, height }
+    }
+
+    /// Returns a reference to the [Landmark] from a map location or None if the location is out of
+    /// range.
+    pub fn get(&self, coordinate: Coordinate) -> Option<&Landmark> {
+        let slope_row = self.details.get(coordinate.y as usize)?;
+        let width = slope_row.len();
+        return (coordinate.x as usize)
+            .checked_rem(width)
+            .and_then(|x| slope_row.get(x));
+    }
+
+    /// The height of the mountain slope that the map covers.
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The width of one repetition of the map, the slope repeats endlessly to the right.
+    pub fn width(&self) -> usize {
+        self.details.first().map_or(0, Vec::len)
+    }
+}
+
+/// Toboggans follow very determinate paths defined by their construction.
+#[derive(Clone, Copy, Debug)]
+pub struct Toboggan {
+    /// The number of map altitude levels this toboggan descends per unit time slice.
+    descent_rate: u32,
+    /// The number of map longitude levels this toboggan slides per unit time slice.
+    slide_rate: u32,
+}
+
+impl Toboggan {
+    pub fn new(descent_rate: u32, slide_rate: u32) -> Self {
+        Self {
+            descent_rate,
+            slide_rate,
+        }
+    }
+
+    /// The endless sequence of locations the toboggan passes, starting at the top left.
+    pub fn slide(&self) -> TobogganPath<'_> {
+        TobogganPath {
+            parent: self,
+            next_pos: Coordinate { x: 0, y: 0 },
+        }
+    }
+}
+
+pub struct TobogganPath<'s> {
+    parent: &'s Toboggan,
+    next_pos: Coordinate,
+}
+
+impl<'s> Iterator for TobogganPath<'s> {
+    type Item = Coordinate;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let yield_pos = self.next_pos;
+        self.next_pos.x += self.parent.slide_rate;
+        self.next_pos.y += self.parent.descent_rate;
+        return Some(yield_pos);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests! {
+        Day3;
+        test_example_part_one: example 1, part One => 7u64;
+        test_example_part_two: example 1, part Two => 336u64;
+        test_example_part_one_tree_free_slope: example 2, part One => 2u64;
+        test_example_part_two_tree_free_slope: example 2, part Two => 0u64;
+    }
+
+    #[test]
+    fn test_part_two_overflow_is_an_error() {
+        let forest = format!("{}\n", "#".repeat(31)).repeat(40_000);
+        let map = parse(InputReader::from_text(forest, String::from("forest"))).unwrap();
+        let e = part_two(&map).unwrap_err();
+        assert_eq!(e.kind(), crate::ErrorKind::Invariant);
+    }
+}
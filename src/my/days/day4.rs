@@ -0,0 +1,434 @@
+//! Day 4: Passport Processing, count the passports that would pass the scanner.
+
+use crate::input::InputReader;
+use crate::solution::{Answer, Solution};
+use std::collections::HashMap;
+use std::ops::Range;
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Passport>;
+
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn parse(&self, reader: InputReader) -> crate::Result<Self::Input> {
+        parse(reader)
+    }
+
+    fn part_one(&self, passports: &Self::Input) -> crate::Result<Answer> {
+        part_one(passports)
+    }
+
+    fn part_two(&self, passports: &Self::Input) -> crate::Result<Answer> {
+        part_two(passports)
+    }
+}
+
+/// Reads blank line separated passports made of whitespace separated `key:value` fields.
+pub fn parse(reader: InputReader) -> crate::Result<Vec<Passport>> {
+    let mut passports = Vec::new();
+    let mut records = reader.records();
+    while let Some(record) = records.next() {
+        let record = record?;
+        let mut builder = PassportBuilder::new();
+
+        // Consume all the passport fields on each line
+        for (i, line) in record.iter().enumerate() {
+            let line_no = records.line_number() + i;
+            let locate = |e: crate::Error, column: usize| {
+                e.with_column(column)
+                    .with_line(line_no, line)
+                    .with_file(records.source())
+            };
+
+            if let Some(column) = line.chars().position(|c| !c.is_ascii()) {
+                return Err(locate(crate::Error::parse("passports must be ASCII"), column + 1));
+            }
+
+            for kv_pair in line.split_whitespace() {
+                // ASCII, so byte offsets are columns
+                let column = kv_pair.as_ptr() as usize - line.as_ptr() as usize + 1;
+                let (key, value) = match kv_pair.split_once(':') {
+                    Some(kv_pair) => kv_pair,
+                    None => {
+                        let e = crate::Error::parse(format!("expected key:value, got {}", kv_pair));
+                        return Err(locate(e, column));
+                    }
+                };
+
+                if let Err(e) = builder.add_field(String::from(key), String::from(value)) {
+                    return Err(locate(e, column));
+                }
+            }
+        }
+
+        passports.push(builder.bind());
+    }
+
+    return Ok(passports);
+}
+
+/// The number of passports with every required field present.
+pub fn part_one(passports: &[Passport]) -> crate::Result<Answer> {
+    let num_complete_passports = passports
+        .iter()
+        .filter(|p| PassportValidator::new(p).check_required_fields())
+        .count();
+    return Ok(Answer::from(num_complete_passports));
+}
+
+/// The number of passports with every required field present and valid.
+pub fn part_two(passports: &[Passport]) -> crate::Result<Answer> {
+    let num_valid_passports = passports
+        .iter()
+        .filter(|p| PassportValidator::new(p).check())
+        .count();
+    return Ok(Answer::from(num_valid_passports));
+}
+
+/// Every field a passport may carry.
+pub const VALID_FIELDS: [&str; 8] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+
+/// Passport fields by key.
+pub type Passport = HashMap<String, String>;
+
+/// Accumulates the fields of one passport, rejecting unknown and duplicate fields.
+#[derive(Default)]
+pub struct PassportBuilder {
+    passport: Passport,
+}
+
+impl PassportBuilder {
+    pub fn add_field(&mut self, key: String, value: String) -> crate::Result<()> {
+        // Validate input key is a known field name
+        if !VALID_FIELDS.iter().any(|f| *f == key) {
+            return Err(crate::Error::validation(format!(
+                "unknown passport field {}",
+                key
+            )));
+        }
+
+        // Validate input key is a new field
+        if self.passport.keys().any(|k| *k == key) {
+            return Err(crate::Error::validation(format!(
+                "duplicate passport field {}",
+                key
+            )));
+        }
+
+        self.passport.insert(key, value);
+        return Ok(());
+    }
+
+    pub fn bind(self) -> Passport {
+        self.passport
+    }
+
+    pub fn new() -> Self {
+        Self {
+            passport: HashMap::new(),
+        }
+    }
+}
+
+/// Checks a [Passport] against the North Pole's (relaxed) passport rules.
+///
+/// Missing fields fail every check that looks at them.
+pub struct PassportValidator<'a> {
+    passport: &'a Passport,
+}
+
+impl<'a> PassportValidator<'a> {
+    pub fn new(passport: &'a Passport) -> Self {
+        Self { passport }
+    }
+
+    /// Whether every required field is present and valid.
+    pub fn check(&self) -> bool {
+        let checks = [
+            Self::check_required_fields,
+            Self::check_byr,
+            Self::check_iyr,
+            Self::check_eyr,
+            Self::check_hgt,
+            Self::check_hcl,
+            Self::check_ecl,
+            Self::check_pid,
+        ];
+
+        return checks.iter().all(|c| c(self));
+    }
+
+    // "cid" is not required.
+    const REQUIRED_FIELDS: [&'static str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+
+    pub fn check_required_fields(&self) -> bool {
+        Self::REQUIRED_FIELDS
+            .iter()
+            .all(|f| self.passport.contains_key(*f))
+    }
+
+    const BYR_RANGE: Range<u32> = 1920..2003;
+
+    pub fn check_byr(&self) -> bool {
+        return self.check_in_range("byr", &Self::BYR_RANGE);
+    }
+
+    const IYR_RANGE: Range<u32> = 2010..2021;
+
+    pub fn check_iyr(&self) -> bool {
+        return self.check_in_range("iyr", &Self::IYR_RANGE);
+    }
+
+    const EYR_RANGE: Range<u32> = 2020..2031;
+
+    pub fn check_eyr(&self) -> bool {
+        return self.check_in_range("eyr", &Self::EYR_RANGE);
+    }
+
+    /// Missing or non-numeric values are never in range.
+    fn check_in_range(&self, key: &str, range: &Range<u32>) -> bool {
+        return match self.passport.get(key).map(|v| v.parse()) {
+            Some(Ok(value)) => range.contains(&value),
+            _ => false,
+        };
+    }
+
+    const HGT_CM_RANGE: Range<u32> = 150..194;
+    const HGT_IN_RANGE: Range<u32> = 59..77;
+
+    pub fn check_hgt(&self) -> bool {
+        let value = match self.passport.get("hgt") {
+            Some(value) => value,
+            None => return false,
+        };
+
+        let (value, range) = if let Some(value) = value.strip_suffix("cm") {
+            (value, &Self::HGT_CM_RANGE)
+        } else if let Some(value) = value.strip_suffix("in") {
+            (value, &Self::HGT_IN_RANGE)
+        } else {
+            return false;
+        };
+
+        return match value.parse() {
+            Ok(value) => range.contains(&value),
+            Err(_) => false,
+        };
+    }
+
+    const HCL_VALID_CHARS: &'static str = "0123456789abcdef";
+    const HCL_VALID_LEN: u8 = 6;
+
+    pub fn check_hcl(&self) -> bool {
+        let value = match self.passport.get("hcl") {
+            Some(value) => value,
+            None => return false,
+        };
+        let mut chars = value.chars();
+
+        // Check for the leading '#'
+        // it is also required to consume this for the next test
+        if let Some(c) = chars.next() {
+            if c != '#' {
+                return false;
+            }
+        } else {
+            return false;
+        }
+
+        // Ensure remaining chars are legal hex
+        if !chars.all(|c| Self::HCL_VALID_CHARS.contains(c)) {
+            return false;
+        }
+
+        // Refresh iterator to count
+        let mut chars = value.chars();
+        // Consume HCL_VALID_LEN chars, nth() is zero indexed but there is also a leading
+        // '#' so they cancel out in length.
+        if chars.nth(Self::HCL_VALID_LEN as usize).is_none() {
+            return false;
+        }
+
+        // There should be no remaining chars
+        if chars.next().is_some() {
+            return false;
+        }
+        return true;
+    }
+
+    const ECL_VALID_ENTRIES: [&'static str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+
+    pub fn check_ecl(&self) -> bool {
+        let value = match self.passport.get("ecl") {
+            Some(value) => value,
+            None => return false,
+        };
+        return Self::ECL_VALID_ENTRIES.iter().any(|entry| *entry == value);
+    }
+
+    pub fn check_pid(&self) -> bool {
+        let value = match self.passport.get("pid") {
+            Some(value) => value,
+            None => return false,
+        };
+
+        if value.len() != 9 {
+            return false;
+        }
+
+        return value.parse::<u32>().is_ok();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests! {
+        Day4;
+        test_example_part_one: example 1, part One => 2u64;
+        test_example_part_two_invalid_passports: example 2, part Two => 0u64;
+        test_example_part_two_valid_passports: example 3, part Two => 4u64;
+    }
+
+    struct Maker {
+        passport: Passport,
+    }
+
+    impl Maker {
+        fn new() -> Self {
+            Maker {
+                passport: Passport::new(),
+            }
+        }
+
+        fn with(mut self, key: &str, value: &str) -> Self {
+            self.passport.insert(String::from(key), String::from(value));
+            self
+        }
+
+        fn done(self) -> Passport {
+            self.passport
+        }
+    }
+
+    mod passport_validator {
+        use super::*;
+
+        #[test]
+        fn test_check_byr_boundaries() {
+            let p = Maker::new().with("byr", "1920").done();
+            assert!(PassportValidator::new(&p).check_byr());
+            let p = Maker::new().with("byr", "2002").done();
+            assert!(PassportValidator::new(&p).check_byr());
+        }
+
+        #[test]
+        fn test_check_byr_out_of_bounds() {
+            let p = Maker::new().with("byr", "2003").done();
+            assert!(!PassportValidator::new(&p).check_byr());
+            let p = Maker::new().with("byr", "1919").done();
+            assert!(!PassportValidator::new(&p).check_byr());
+        }
+
+        #[test]
+        fn test_check_byr_rejects_missing_and_non_numeric() {
+            let p = Maker::new().done();
+            assert!(!PassportValidator::new(&p).check_byr());
+            let p = Maker::new().with("byr", "19x0").done();
+            assert!(!PassportValidator::new(&p).check_byr());
+        }
+
+        #[test]
+        fn test_check_hgt_boundaries() {
+            // cm
+            let p = Maker::new().with("hgt", "150cm").done();
+            assert!(PassportValidator::new(&p).check_hgt());
+            let p = Maker::new().with("hgt", "193cm").done();
+            assert!(PassportValidator::new(&p).check_hgt());
+
+            // cm
+            let p = Maker::new().with("hgt", "59in").done();
+            assert!(PassportValidator::new(&p).check_hgt());
+            let p = Maker::new().with("hgt", "76in").done();
+            assert!(PassportValidator::new(&p).check_hgt());
+        }
+
+        #[test]
+        fn test_check_hgt_out_of_bounds() {
+            // cm
+            let p = Maker::new().with("hgt", "149cm").done();
+            assert!(!PassportValidator::new(&p).check_hgt());
+            let p = Maker::new().with("hgt", "194cm").done();
+            assert!(!PassportValidator::new(&p).check_hgt());
+
+            // cm
+            let p = Maker::new().with("hgt", "58in").done();
+            assert!(!PassportValidator::new(&p).check_hgt());
+            let p = Maker::new().with("hgt", "77in").done();
+            assert!(!PassportValidator::new(&p).check_hgt());
+        }
+
+        #[test]
+        fn test_check_hcl_accepts_all_hex_characters() {
+            let p = Maker::new().with("hcl", "#abcdef").done();
+            assert!(PassportValidator::new(&p).check_hcl());
+            let p = Maker::new().with("hcl", "#012345").done();
+            assert!(PassportValidator::new(&p).check_hcl());
+            let p = Maker::new().with("hcl", "#6789ab").done();
+            assert!(PassportValidator::new(&p).check_hcl());
+        }
+
+        #[test]
+        fn test_check_hcl_rejects_non_hex_characters() {
+            let p = Maker::new().with("hcl", "#xve--@").done();
+            assert!(!PassportValidator::new(&p).check_hcl());
+        }
+
+        #[test]
+        fn test_check_hcl_requires_hash_prefix() {
+            let p = Maker::new().with("hcl", "abcdef0").done();
+            assert!(!PassportValidator::new(&p).check_hcl());
+
+            let p = Maker::new().with("hcl", "a#cdef0").done();
+            assert!(!PassportValidator::new(&p).check_hcl());
+        }
+
+        #[test]
+        fn test_check_hcl_requires_6_characters() {
+            let p = Maker::new().with("hcl", "#0123").done();
+            assert!(!PassportValidator::new(&p).check_hcl());
+
+            let p = Maker::new().with("hcl", "#abcdef0").done();
+            assert!(!PassportValidator::new(&p).check_hcl());
+        }
+
+        #[test]
+        fn test_check_ecl_accepts_all_valid_colors() {
+            const VALID_ECL: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+            for color in VALID_ECL.iter() {
+                let p = Maker::new().with("ecl", color).done();
+                assert!(PassportValidator::new(&p).check_ecl());
+            }
+        }
+
+        #[test]
+        fn test_check_ecl_rejects_other_colors() {
+            let p = Maker::new().with("ecl", "red").done();
+            assert!(!PassportValidator::new(&p).check_ecl());
+        }
+
+        #[test]
+        fn test_check_pid_accepts_any_9_digit_number() {
+            let p = Maker::new().with("pid", "915789426").done();
+            assert!(PassportValidator::new(&p).check_pid());
+
+            let p = Maker::new().with("pid", "000789426").done();
+            assert!(PassportValidator::new(&p).check_pid());
+        }
+    }
+}
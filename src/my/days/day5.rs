@@ -0,0 +1,477 @@
+//! Day 5: Binary Boarding, decode binary space partitioned boarding passes to find your seat.
+
+use crate::input::InputReader;
+use crate::solution::{Answer, Part, Solution};
+use std::cmp;
+use std::cmp::Ordering;
+use std::collections::BTreeSet;
+use std::ops;
+use std::ops::Bound::{Excluded, Unbounded};
+use std::ops::RangeBounds;
+use std::str::FromStr;
+
+const PLANE_ROWS: u16 = 128;
+const PLANE_COLS: u16 = 8;
+const BOARDING_PASS_LEN: usize = 10;
+
+/// A row or column index on the plane.
+pub type PlaneIndex = u16;
+pub type SeatId = u64;
+/// Disjoint, non-adjacent ranges of seat ids, see [range_insert].
+pub type Set = BTreeSet<SeatIdRange>;
+type Range = ops::Range<PlaneIndex>;
+
+/// A seat on the plane, decoded from its boarding pass.
+#[derive(Debug)]
+pub struct Seat {
+    boarding_pass: String,
+    row: PlaneIndex,
+    column: PlaneIndex,
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<Seat>;
+
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn parse(&self, reader: InputReader) -> crate::Result<Self::Input> {
+        parse(reader)
+    }
+
+    fn part_one(&self, seats: &Self::Input) -> crate::Result<Answer> {
+        part_one(seats)
+    }
+
+    fn part_two(&self, seats: &Self::Input) -> crate::Result<Answer> {
+        part_two(seats)
+    }
+
+    fn reference(&self, seats: &Self::Input, part: Part) -> Option<crate::Result<Answer>> {
+        match part {
+            Part::One => None,
+            Part::Two => Some(reference_part_two(seats)),
+        }
+    }
+}
+
+/// Reads one boarding pass per line.
+pub fn parse(reader: InputReader) -> crate::Result<Vec<Seat>> {
+    reader.parse_lines::<Seat>().collect()
+}
+
+/// The highest seat id on any boarding pass.
+pub fn part_one(seats: &[Seat]) -> crate::Result<Answer> {
+    let highest_seat_id = seats.iter().map(Seat::get_id).max().unwrap_or(0);
+    return Ok(Answer::from(highest_seat_id));
+}
+
+/// The id of the one free seat, see [find_your_seat].
+pub fn part_two(seats: &[Seat]) -> crate::Result<Answer> {
+    Ok(Answer::from(find_your_seat(seats)?))
+}
+
+/// Finds the single unoccupied seat id between the occupied ones of a full flight.
+pub fn find_your_seat(seats: &[Seat]) -> crate::Result<SeatId> {
+    // Build contiguous seat ranges
+    let mut seat_id_ranges = Set::new();
+    for seat in seats.iter() {
+        range_insert(&mut seat_id_ranges, seat.get_id());
+    }
+
+    // In the end, there are just two contiguous ranges because this is a fully booked plane, your
+    // seat is between them.
+    //
+    // Check there are exactly two and their distance is 1
+    let mut ranges = seat_id_ranges.iter();
+    let (before, after) = match (ranges.next(), ranges.next(), ranges.next()) {
+        (Some(before), Some(after), None) => (before, after),
+        _ => {
+            return Err(crate::Error::invariant(format!(
+                "expected exactly one gap in the seat ids, found {} contiguous ranges",
+                seat_id_ranges.len()
+            )))
+        }
+    };
+    if range_distance(before, after) != 1 {
+        return Err(crate::Error::invariant(format!(
+            "expected a single missing seat, found a gap of {}",
+            range_distance(before, after)
+        )));
+    }
+
+    return Ok(before.end);
+}
+
+/// Finds the free seat by sorting the seat ids and looking for the one gap, see [find_your_seat].
+pub fn reference_part_two(seats: &[Seat]) -> crate::Result<Answer> {
+    let mut ids: Vec<SeatId> = seats.iter().map(Seat::get_id).collect();
+    ids.sort_unstable();
+    ids.dedup();
+
+    let gaps: Vec<&[SeatId]> = ids.windows(2).filter(|w| w[1] - w[0] > 1).collect();
+    return match gaps.as_slice() {
+        [gap] if gap[1] - gap[0] == 2 => Ok(Answer::from(gap[0] + 1)),
+        _ => Err(crate::Error::invariant("expected exactly one free seat")),
+    };
+}
+
+impl Seat {
+    fn new(boarding_pass: String, row: PlaneIndex, column: PlaneIndex) -> Self {
+        Self {
+            boarding_pass,
+            row,
+            column,
+        }
+    }
+
+    /// The boarding pass this seat was decoded from.
+    pub fn boarding_pass(&self) -> &str {
+        &self.boarding_pass
+    }
+
+    pub fn row(&self) -> PlaneIndex {
+        self.row
+    }
+
+    pub fn column(&self) -> PlaneIndex {
+        self.column
+    }
+
+    pub fn get_id(&self) -> SeatId {
+        ((self.row * 8) + self.column) as SeatId
+    }
+}
+
+impl FromStr for Seat {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        let len = s.chars().count();
+        if len != BOARDING_PASS_LEN {
+            let e = crate::Error::parse(format!(
+                "boarding pass must be {} characters, got {}",
+                BOARDING_PASS_LEN, len
+            ));
+            return Err(e.with_column(cmp::min(len, BOARDING_PASS_LEN) + 1));
+        }
+
+        let row = Self::parse_row(s)?;
+        let column = Self::parse_column(s)?;
+        return Ok(Self::new(String::from(s), row, column));
+    }
+}
+
+// FromStr helpers
+impl Seat {
+    fn parse_row(s: &str) -> crate::Result<PlaneIndex> {
+        return reduce(s.chars().enumerate().take(7), 0..PLANE_ROWS, 'F', 'B');
+    }
+
+    fn parse_column(s: &str) -> crate::Result<PlaneIndex> {
+        return reduce(s.chars().enumerate().skip(7), 0..PLANE_COLS, 'L', 'R');
+    }
+}
+
+fn unwrap_range(range: Range) -> crate::Result<PlaneIndex> {
+    if range.start_bound() == Unbounded || range.end_bound() == Unbounded {
+        return Err(crate::Error::invariant("unbounded seat range"));
+    }
+
+    if range.start >= range.end || range.end - range.start != 1 {
+        return Err(crate::Error::parse("boarding pass does not narrow to a single seat"));
+    }
+
+    return Ok(range.start);
+}
+
+fn reduce<I>(
+    mut it: I,
+    mut range: Range,
+    lower_specifier: char,
+    upper_specifier: char,
+) -> crate::Result<PlaneIndex>
+where
+    I: Iterator<Item = (usize, char)>,
+{
+    range = it.try_fold(range, |r, (index, elem)| {
+        let result = decide(elem, &r, lower_specifier, upper_specifier);
+        return result.map_err(|e| e.with_column(index + 1));
+    })?;
+    return unwrap_range(range);
+}
+
+fn decide(
+    specifier: char,
+    range: &Range,
+    lower_specifier: char,
+    upper_specifier: char,
+) -> crate::Result<Range> {
+    let mid = range.len() / 2;
+    let mid = range.start + mid as PlaneIndex;
+    match specifier {
+        c if c == lower_specifier => Ok(range.start..mid),
+        c if c == upper_specifier => Ok(mid..range.end),
+        _ => Err(crate::Error::parse(format!(
+            "unknown airline specifier {:?}, expected {:?} or {:?}",
+            specifier, lower_specifier, upper_specifier
+        ))),
+    }
+}
+
+/// Adds `id` to `s`, merging it with any ranges it extends so ranges never touch.
+pub fn range_insert(s: &mut Set, id: SeatId) {
+    let id_as_range = SeatIdRange::from(id);
+
+    // Some(SeatIdRange) if we should grow this range tail
+    let before_range = match s.range((Unbounded, Excluded(&id_as_range))).next_back() {
+        Some(&range) => {
+            if range.end == id {
+                Some(range)
+            } else {
+                None
+            }
+        }
+        None => None,
+    };
+
+    // Some(SeatIdRange) if we should grow this range head
+    let after_range = match s.range((Excluded(&id_as_range), Unbounded)).next() {
+        Some(&range) => {
+            if range.start == id + 1 {
+                Some(range)
+            } else {
+                None
+            }
+        }
+        None => None,
+    };
+
+    // Build the new range to insert, remove mergeable ranges as needed
+    let mut range_accum = id_as_range;
+
+    if let Some(before_range) = before_range {
+        if range_can_merge(&id_as_range, &before_range) {
+            range_accum = range_merge(&range_accum, &before_range);
+            s.remove(&before_range);
+        }
+    }
+
+    if let Some(after_range) = after_range {
+        if range_can_merge(&id_as_range, &after_range) {
+            range_accum = range_merge(&range_accum, &after_range);
+            s.remove(&after_range);
+        }
+    }
+
+    s.insert(range_accum);
+}
+
+fn range_can_merge(l: &SeatIdRange, r: &SeatIdRange) -> bool {
+    (l.start <= r.start && l.end >= r.start) || (l.start <= r.end && l.end >= r.end)
+}
+
+fn range_merge(l: &SeatIdRange, r: &SeatIdRange) -> SeatIdRange {
+    SeatIdRange::new(cmp::min(l.start, r.start), cmp::max(l.end, r.end))
+}
+
+/// The number of seat ids between the end of one range and the start of the other.
+pub fn range_distance(l: &SeatIdRange, r: &SeatIdRange) -> usize {
+    if range_can_merge(l, r) {
+        0
+    } else if l < r {
+        (r.start - l.end) as usize
+    } else if r < l {
+        (l.start - r.end) as usize
+    } else {
+        panic!("equal ranges not covered by merge");
+    }
+}
+
+/// Range variant that provides a total ordering on range starts.
+///
+/// SeatIdRange is always [start, end)
+#[derive(Copy, Clone, Eq, Debug)]
+pub struct SeatIdRange {
+    start: SeatId,
+    end: SeatId,
+}
+
+impl SeatIdRange {
+    pub fn new(start: SeatId, end: SeatId) -> Self {
+        Self { start, end }
+    }
+
+    pub fn start(&self) -> SeatId {
+        self.start
+    }
+
+    pub fn end(&self) -> SeatId {
+        self.end
+    }
+}
+
+impl From<SeatId> for SeatIdRange {
+    fn from(id: SeatId) -> Self {
+        Self::new(id, id + 1)
+    }
+}
+
+impl Ord for SeatIdRange {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start.cmp(&other.start)
+    }
+}
+
+impl PartialOrd for SeatIdRange {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for SeatIdRange {
+    fn eq(&self, other: &Self) -> bool {
+        self.start == other.start
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests! {
+        Day5;
+        test_example_part_one: example 1, part One => 820u64;
+    }
+
+    #[test]
+    fn test_known_inputs() {
+        assert_eq!(make_seat("BFFFBBFRRR").row, 70);
+        assert_eq!(make_seat("BFFFBBFRRR").column, 7);
+        assert_eq!(make_seat("BFFFBBFRRR").get_id(), 567);
+
+        assert_eq!(make_seat("FFFBBBFRRR").row, 14);
+        assert_eq!(make_seat("FFFBBBFRRR").column, 7);
+        assert_eq!(make_seat("FFFBBBFRRR").get_id(), 119);
+
+        assert_eq!(make_seat("BBFFBBFRLL").row, 102);
+        assert_eq!(make_seat("BBFFBBFRLL").column, 4);
+        assert_eq!(make_seat("BBFFBBFRLL").get_id(), 820);
+    }
+
+    #[test]
+    fn test_rejects_unknown_specifier() {
+        let e = "BFXBFBFLLR".parse::<Seat>().unwrap_err();
+        assert_eq!(e.kind(), crate::ErrorKind::Parse);
+        assert_eq!(e.location().unwrap().column, Some(3));
+    }
+
+    #[test]
+    fn test_rejects_wrong_length() {
+        assert!("BFFFBBFRR".parse::<Seat>().is_err());
+        assert!("BFFFBBFRRRR".parse::<Seat>().is_err());
+    }
+
+    fn make_seat(s: &'static str) -> Seat {
+        s.parse::<Seat>().expect("failed to parse")
+    }
+
+    mod seat_id_range {
+        use super::*;
+
+        #[test]
+        fn test_empty_insert() {
+            let mut s = Set::new();
+            let id = 10;
+            range_insert(&mut s, id);
+
+            let id_range = SeatIdRange::from(id);
+            assert_eq!(s.get(&id_range), Some(&id_range));
+        }
+
+        #[test]
+        fn test_disjoint_insert() {
+            let mut s = Set::new();
+            let id = 10;
+            let other_id = 15;
+            range_insert(&mut s, id);
+            range_insert(&mut s, other_id);
+
+            let mut contents = s.iter();
+            assert_eq!(contents.next(), Some(&SeatIdRange::from(id)));
+            assert_eq!(contents.next(), Some(&SeatIdRange::from(other_id)));
+            assert_eq!(contents.next(), None);
+        }
+
+        #[test]
+        fn test_merging_lower_bound() {
+            let mut s = Set::new();
+            let id = 10;
+            let other_id = 9;
+            range_insert(&mut s, id);
+            range_insert(&mut s, other_id);
+
+            let mut contents = s.iter();
+            assert_eq!(contents.next(), Some(&SeatIdRange::new(9, 11)));
+            assert_eq!(contents.next(), None);
+        }
+
+        #[test]
+        fn test_merging_upper_bound() {
+            let mut s = Set::new();
+            let id = 10;
+            let other_id = 11;
+            range_insert(&mut s, id);
+            range_insert(&mut s, other_id);
+
+            let mut contents = s.iter();
+            assert_eq!(contents.next(), Some(&SeatIdRange::new(10, 12)));
+            assert_eq!(contents.next(), None);
+        }
+
+        #[test]
+        fn test_merging_upper_and_lower_bound() {
+            let mut s = Set::new();
+            let id = 10;
+            let other_id = 12;
+            let joining_id = 11;
+            range_insert(&mut s, id);
+            range_insert(&mut s, other_id);
+            range_insert(&mut s, joining_id);
+
+            let mut contents = s.iter();
+            assert_eq!(contents.next(), Some(&SeatIdRange::new(10, 13)));
+            assert_eq!(contents.next(), None);
+        }
+
+        #[test]
+        fn test_merging_equal_ranges() {
+            let mut s = Set::new();
+            let id = 10;
+            let other_id = 10;
+            range_insert(&mut s, id);
+            range_insert(&mut s, other_id);
+
+            let mut contents = s.iter();
+            assert_eq!(contents.next(), Some(&SeatIdRange::new(10, 11)));
+            assert_eq!(contents.next(), None);
+        }
+
+        #[test]
+        fn test_merge_boundaries() {
+            let mut s = Set::new();
+            let id = 10;
+            let other_id = 12;
+            range_insert(&mut s, id);
+            range_insert(&mut s, other_id);
+
+            let mut contents = s.iter();
+            assert_eq!(contents.next(), Some(&SeatIdRange::from(id)));
+            assert_eq!(contents.next(), Some(&SeatIdRange::from(other_id)));
+            assert_eq!(contents.next(), None);
+        }
+    }
+}
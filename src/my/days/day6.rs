@@ -0,0 +1,114 @@
+//! Day 6: Custom Customs, tally the customs declaration questions answered by each group.
+
+use crate::input::InputReader;
+use crate::solution::{Answer, Solution};
+use std::collections::BTreeSet;
+
+/// The questions one passenger answered "yes" to.
+pub type Answers = BTreeSet<char>;
+
+/// The answers of every passenger in a travel group.
+pub struct GroupAnswers {
+    answers: Vec<Answers>,
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<GroupAnswers>;
+
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn parse(&self, reader: InputReader) -> crate::Result<Self::Input> {
+        parse(reader)
+    }
+
+    fn part_one(&self, groups: &Self::Input) -> crate::Result<Answer> {
+        part_one(groups)
+    }
+
+    fn part_two(&self, groups: &Self::Input) -> crate::Result<Answer> {
+        part_two(groups)
+    }
+}
+
+/// Reads blank line separated groups, one line of "yes" answers per passenger.
+pub fn parse(reader: InputReader) -> crate::Result<Vec<GroupAnswers>> {
+    let mut groups = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let mut group = GroupAnswers::new();
+        for line in record {
+            let passenger_answers: Answers = line.chars().collect();
+            group.answers.push(passenger_answers);
+        }
+        groups.push(group);
+    }
+
+    return Ok(groups);
+}
+
+/// The sum over groups of questions anyone in the group answered "yes" to.
+pub fn part_one(groups: &[GroupAnswers]) -> crate::Result<Answer> {
+    let magic_sum: u64 = groups.iter().map(tally_group_any).sum();
+    return Ok(Answer::from(magic_sum));
+}
+
+/// The sum over groups of questions everyone in the group answered "yes" to.
+pub fn part_two(groups: &[GroupAnswers]) -> crate::Result<Answer> {
+    let magic_sum: u64 = groups.iter().map(tally_group).sum();
+    return Ok(Answer::from(magic_sum));
+}
+
+/// Returns the number of questions any group member responded 'True' to.
+pub fn tally_group_any(g: &GroupAnswers) -> u64 {
+    let any_answers = g
+        .answers
+        .iter()
+        .fold(Answers::new(), |accum, member_answers| {
+            accum.union(member_answers).cloned().collect()
+        });
+
+    return any_answers.len() as u64;
+}
+
+/// Returns the number of questions every group member responded 'True' to.
+pub fn tally_group(g: &GroupAnswers) -> u64 {
+    if g.answers.is_empty() {
+        return 0;
+    }
+
+    let mut answers = g.answers.iter();
+    let initial: Answers = answers.next().unwrap().clone();
+    let common_answers = answers.fold(initial, |accum, member_answers| {
+        accum.intersection(member_answers).cloned().collect()
+    });
+
+    return common_answers.len() as u64;
+}
+
+impl GroupAnswers {
+    fn new() -> Self {
+        Self {
+            answers: Vec::new(),
+        }
+    }
+
+    /// Each passenger's answers, in input order.
+    pub fn answers(&self) -> &[Answers] {
+        &self.answers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    crate::example_tests! {
+        Day6;
+        test_example_part_one: example 1, part One => 11u64;
+        test_example_part_two: example 1, part Two => 6u64;
+    }
+}
@@ -0,0 +1,251 @@
+//! Differential testing of each day's solver against its reference solution on generated inputs,
+//! see [Solution::reference](crate::solution::Solution::reference) and [find].
+
+use crate::generate::{self, Generator, Params, Rng};
+use crate::input::InputReader;
+use crate::solution::{Answer, Part, Puzzle};
+use crate::{days, Error};
+use std::fmt;
+use std::ops::Range;
+
+/// An input the solver and the reference solution disagree on.
+#[derive(Debug)]
+pub struct Divergence {
+    pub day: u8,
+    pub part: Part,
+    /// The seed the input was generated from, before it was shrunk.
+    pub seed: u64,
+    pub input: String,
+    pub solver: crate::Result<Answer>,
+    pub reference: crate::Result<Answer>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(
+            f,
+            "Day {} part {} diverges on seed {}, shrunk to {} lines",
+            self.day,
+            self.part,
+            self.seed,
+            self.input.lines().count()
+        )?;
+        writeln!(f, "solver:    {}", Outcome(&self.solver))?;
+        writeln!(f, "reference: {}", Outcome(&self.reference))?;
+        return write!(f, "input:\n{}", self.input);
+    }
+}
+
+/// Displays a solution's answer or error.
+struct Outcome<'a>(&'a crate::Result<Answer>);
+
+impl fmt::Display for Outcome<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Ok(answer) => write!(f, "{}", answer),
+            Err(e) => write!(f, "{} error: {}", e.kind().name(), e),
+        }
+    }
+}
+
+/// Runs `day`'s solver and reference solution on the inputs generated from `seeds`.
+///
+/// Returns the first input they disagree on, shrunk, or an error if the day can not be tested.
+pub fn check(day: u8, seeds: Range<u64>, params: &Params) -> crate::Result<Option<Divergence>> {
+    let puzzle = days::get(day);
+    let generator = generate::get(day);
+    let (puzzle, generator) = match (puzzle, generator) {
+        (Some(puzzle), Some(generator)) => (puzzle, generator),
+        _ => {
+            return Err(Error::validation(format!(
+                "day {} has no input generator",
+                day
+            )))
+        }
+    };
+    return find(puzzle, generator, seeds, params);
+}
+
+/// Like [check], for any puzzle and generator.
+pub fn find(
+    puzzle: &dyn Puzzle,
+    generator: &dyn Generator,
+    seeds: Range<u64>,
+    params: &Params,
+) -> crate::Result<Option<Divergence>> {
+    let mut compared = false;
+    for seed in seeds {
+        let generated = generator.generate(&mut Rng::new(seed), params);
+        for part in Part::ALL.iter().copied() {
+            let (solver, reference) = match solve(puzzle, &generated.text, part) {
+                Some(outcomes) => outcomes,
+                None => continue,
+            };
+            compared = true;
+            if agree(&solver, &reference) {
+                continue;
+            }
+
+            let input = shrink(puzzle, part, &generated.text);
+            let (solver, reference) =
+                solve(puzzle, &input, part).expect("shrinking keeps a divergence");
+            return Ok(Some(Divergence {
+                day: puzzle.day(),
+                part,
+                seed,
+                input,
+                solver,
+                reference,
+            }));
+        }
+    }
+
+    if !compared {
+        return Err(Error::validation(format!(
+            "day {} has no reference solution",
+            puzzle.day()
+        )));
+    }
+    return Ok(None);
+}
+
+/// Removes lines from `text`, a diverging input, for as long as it keeps diverging.
+///
+/// The result diverges and removing any one of its lines makes it agree.
+pub fn shrink(puzzle: &dyn Puzzle, part: Part, text: &str) -> String {
+    let mut lines: Vec<&str> = text.lines().collect();
+    let mut chunk = lines.len() / 2;
+    while chunk > 0 {
+        let mut removed = false;
+        let mut start = 0;
+        while start < lines.len() {
+            let end = (start + chunk).min(lines.len());
+            let candidate: Vec<&str> = [&lines[..start], &lines[end..]].concat();
+            if diverges(puzzle, part, &join(&candidate)) {
+                lines = candidate;
+                removed = true;
+            } else {
+                start = end;
+            }
+        }
+        // Halve the chunk size only once no chunk of this size can go
+        if !removed {
+            chunk /= 2;
+        }
+    }
+    return join(&lines);
+}
+
+fn diverges(puzzle: &dyn Puzzle, part: Part, text: &str) -> bool {
+    match solve(puzzle, text, part) {
+        Some((solver, reference)) => !agree(&solver, &reference),
+        None => false,
+    }
+}
+
+/// Solves `part` of `text` with the solver and the reference, or None if it does not parse or
+/// there is no reference.
+fn solve(
+    puzzle: &dyn Puzzle,
+    text: &str,
+    part: Part,
+) -> Option<(crate::Result<Answer>, crate::Result<Answer>)> {
+    let reader = InputReader::from_text(String::from(text), String::from("generated input"));
+    let input = puzzle.parse(reader).ok()?;
+    let reference = puzzle.reference(input.as_ref(), part)?;
+    return Some((puzzle.solve(input.as_ref(), part), reference));
+}
+
+/// Whether two outcomes are the same answer or the same kind of error.
+fn agree(solver: &crate::Result<Answer>, reference: &crate::Result<Answer>) -> bool {
+    match (solver, reference) {
+        (Ok(solver), Ok(reference)) => solver == reference,
+        (Err(solver), Err(reference)) => solver.kind() == reference.kind(),
+        _ => false,
+    }
+}
+
+fn join(lines: &[&str]) -> String {
+    let mut text = lines.join("\n");
+    if !text.is_empty() {
+        text.push('\n');
+    }
+    return text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::solution::Solution;
+
+    /// Sums the entries, but the fast way forgets the ones over 1900.
+    struct Buggy;
+
+    impl Solution for Buggy {
+        type Input = Vec<i64>;
+
+        fn day(&self) -> u8 {
+            1
+        }
+
+        fn parse(&self, reader: InputReader) -> crate::Result<Self::Input> {
+            reader.parse_lines().collect()
+        }
+
+        fn part_one(&self, input: &Self::Input) -> crate::Result<Answer> {
+            Ok(Answer::from(
+                input.iter().filter(|&&n| n <= 1900).sum::<i64>(),
+            ))
+        }
+
+        fn part_two(&self, _input: &Self::Input) -> crate::Result<Answer> {
+            Ok(Answer::Unsolved)
+        }
+
+        fn reference(&self, input: &Self::Input, part: Part) -> Option<crate::Result<Answer>> {
+            match part {
+                Part::One => Some(Ok(Answer::from(input.iter().sum::<i64>()))),
+                Part::Two => None,
+            }
+        }
+    }
+
+    #[test]
+    fn test_finds_and_shrinks_divergence() {
+        let generator = generate::get(1).unwrap();
+        let divergence = find(&Buggy, generator, 0..10, &Params::default())
+            .unwrap()
+            .unwrap();
+        assert_eq!(divergence.part, Part::One);
+        assert_eq!(divergence.seed, 0);
+        assert_eq!(divergence.input.lines().count(), 1);
+        assert!(divergence.input.trim().parse::<i64>().unwrap() > 1900);
+        assert!(divergence.solver.is_ok() && divergence.reference.is_ok());
+    }
+
+    #[test]
+    fn test_reference_solutions_agree() {
+        for day in [1, 5].iter().copied() {
+            let params = Params {
+                size: 50,
+                ..Params::default()
+            };
+            assert!(check(day, 0..20, &params).unwrap().is_none(), "day {}", day);
+            let invalid = Params {
+                invalid: true,
+                ..params
+            };
+            assert!(
+                check(day, 0..20, &invalid).unwrap().is_none(),
+                "day {}",
+                day
+            );
+        }
+    }
+
+    #[test]
+    fn test_requires_a_reference() {
+        let e = check(6, 0..1, &Params::default()).unwrap_err();
+        assert_eq!(e.kind(), crate::ErrorKind::Validation);
+    }
+}
@@ -1,22 +1,294 @@
+use std::convert::Infallible;
 use std::fmt;
 use std::error;
+use std::io;
+use std::num;
 use std::result;
+use std::str;
+
+/// Broad category of an [Error], lets callers tell bad input apart from bugs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ErrorKind {
+    /// Reading or writing failed.
+    Io,
+    /// Input is not in the expected format.
+    Parse,
+    /// Input is well formed but its contents are not acceptable.
+    Validation,
+    /// Input breaks an assumption the puzzle promised would hold.
+    Invariant,
+}
+
+impl ErrorKind {
+    /// Process exit code for a failure of this kind, distinct per kind.
+    ///
+    /// 2 is reserved for command line usage errors and 101 is what a panic, i.e. a bug, exits
+    /// with.
+    pub fn exit_code(self) -> i32 {
+        match self {
+            ErrorKind::Io => 3,
+            ErrorKind::Parse => 4,
+            ErrorKind::Validation => 5,
+            ErrorKind::Invariant => 6,
+        }
+    }
+
+    /// Short machine readable name of the kind, e.g. `parse`.
+    pub fn name(self) -> &'static str {
+        match self {
+            ErrorKind::Io => "io",
+            ErrorKind::Parse => "parse",
+            ErrorKind::Validation => "validation",
+            ErrorKind::Invariant => "invariant",
+        }
+    }
+}
+
+impl fmt::Display for ErrorKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match self {
+            ErrorKind::Io => "I/O error",
+            ErrorKind::Parse => "parse error",
+            ErrorKind::Validation => "validation error",
+            ErrorKind::Invariant => "puzzle invariant violated",
+        };
+        write!(f, "{}", name)
+    }
+}
 
 #[derive(Debug)]
-pub struct Error {}
+pub struct Error {
+    kind: ErrorKind,
+    message: String,
+    source: Option<Box<dyn error::Error + Send + Sync>>,
+    location: Option<Location>,
+}
+
+/// Where in the input an [Error] was found.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Location {
+    /// Name of the input, usually its path.
+    pub file: Option<String>,
+    /// 1-based line number.
+    pub line: usize,
+    /// 1-based column, counted in characters.
+    pub column: Option<usize>,
+    /// Text of the offending line.
+    pub text: Option<String>,
+}
 
 pub type Result<T> = result::Result<T, Error>;
 
 impl Error {
-    pub fn new() -> Self {
-        Self {}
+    pub fn new<S: Into<String>>(kind: ErrorKind, message: S) -> Self {
+        Self {
+            kind,
+            message: message.into(),
+            source: None,
+            location: None,
+        }
+    }
+
+    pub fn io<S: Into<String>>(message: S) -> Self {
+        Self::new(ErrorKind::Io, message)
+    }
+
+    pub fn parse<S: Into<String>>(message: S) -> Self {
+        Self::new(ErrorKind::Parse, message)
+    }
+
+    pub fn validation<S: Into<String>>(message: S) -> Self {
+        Self::new(ErrorKind::Validation, message)
+    }
+
+    pub fn invariant<S: Into<String>>(message: S) -> Self {
+        Self::new(ErrorKind::Invariant, message)
+    }
+
+    /// Chains the lower level error that caused this one.
+    pub fn with_source<E>(mut self, source: E) -> Self
+    where
+        E: error::Error + Send + Sync + 'static,
+    {
+        self.source = Some(Box::new(source));
+        self
+    }
+
+    pub fn at(mut self, location: Location) -> Self {
+        self.location = Some(location);
+        self
+    }
+
+    /// Attaches the 1-based input line number and the line's text, keeping any known column.
+    pub fn with_line(mut self, number: usize, text: &str) -> Self {
+        let location = self.location.get_or_insert_with(Location::default);
+        location.line = number;
+        location.text = Some(String::from(text));
+        self
+    }
+
+    /// Attaches the 1-based column, in characters, the problem was found at.
+    pub fn with_column(mut self, column: usize) -> Self {
+        self.location.get_or_insert_with(Location::default).column = Some(column);
+        self
+    }
+
+    /// Names the input the error was found in.
+    pub fn with_file<S: Into<String>>(mut self, file: S) -> Self {
+        self.location.get_or_insert_with(Location::default).file = Some(file.into());
+        self
+    }
+
+    pub fn kind(&self) -> ErrorKind {
+        self.kind
+    }
+
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+
+    pub fn location(&self) -> Option<&Location> {
+        self.location.as_ref()
+    }
+
+    pub fn line_number(&self) -> Option<usize> {
+        match &self.location {
+            Some(location) if location.line != 0 => Some(location.line),
+            _ => None,
+        }
     }
 }
 
-impl error::Error for Error {}
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match &self.source {
+            Some(source) => Some(source.as_ref()),
+            None => None,
+        }
+    }
+}
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "()")
+        write!(f, "{}", self.message)?;
+        if let Some(source) = &self.source {
+            write!(f, ": {}", source)?;
+        }
+
+        if let Some(location) = &self.location {
+            write!(f, "\n{}", location)?;
+        }
+        return Ok(());
+    }
+}
+
+impl fmt::Display for Location {
+    /// Renders a compiler style snippet with a caret under the offending column, or under the
+    /// whole line if the column is not known.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let file = self.file.as_deref().unwrap_or("<input>");
+        let line_label = self.line.to_string();
+        let gutter = " ".repeat(line_label.len());
+
+        // Line 0 is an unknown line, e.g. for a value parsed on its own
+        write!(f, "{}--> {}", gutter, file)?;
+        match (self.line, self.column) {
+            (0, Some(column)) => write!(f, ", column {}", column)?,
+            (0, None) => (),
+            (line, Some(column)) => write!(f, ":{}:{}", line, column)?,
+            (line, None) => write!(f, ":{}", line)?,
+        }
+
+        if let Some(text) = &self.text {
+            write!(f, "\n{} |\n{} | {}\n{} | ", gutter, line_label, text, gutter)?;
+            match self.column {
+                Some(column) => {
+                    write!(f, "{}^", " ".repeat(column.saturating_sub(1)))?;
+                }
+                None => {
+                    write!(f, "{}", "^".repeat(text.chars().count().max(1)))?;
+                }
+            }
+        }
+        return Ok(());
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Self::io("input could not be read").with_source(e)
+    }
+}
+
+impl From<num::ParseIntError> for Error {
+    fn from(e: num::ParseIntError) -> Self {
+        Self::parse("invalid integer").with_source(e)
+    }
+}
+
+impl From<num::ParseFloatError> for Error {
+    fn from(e: num::ParseFloatError) -> Self {
+        Self::parse("invalid number").with_source(e)
+    }
+}
+
+impl From<str::ParseBoolError> for Error {
+    fn from(e: str::ParseBoolError) -> Self {
+        Self::parse("invalid boolean").with_source(e)
+    }
+}
+
+impl From<std::char::ParseCharError> for Error {
+    fn from(e: std::char::ParseCharError) -> Self {
+        Self::parse("invalid character").with_source(e)
+    }
+}
+
+impl From<Infallible> for Error {
+    fn from(e: Infallible) -> Self {
+        match e {}
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_renders_caret_under_column() {
+        let e = Error::parse("unknown airline specifier 'X'")
+            .with_column(3)
+            .with_line(12, "BFXBFBFLLR")
+            .with_file("inputs/day_5");
+        assert_eq!(e.kind(), ErrorKind::Parse);
+        assert_eq!(
+            e.to_string(),
+            "unknown airline specifier 'X'\n\
+             \x20 --> inputs/day_5:12:3\n\
+             \x20  |\n\
+             12 | BFXBFBFLLR\n\
+             \x20  |   ^"
+        );
+    }
+
+    #[test]
+    fn test_underlines_whole_line_without_column() {
+        let e = Error::validation("bad").with_line(2, "abc");
+        assert_eq!(e.to_string(), "bad\n --> <input>:2\n  |\n2 | abc\n  | ^^^");
+    }
+
+    #[test]
+    fn test_omits_unknown_line() {
+        let e = Error::parse("bad").with_column(6);
+        assert_eq!(e.line_number(), None);
+        assert_eq!(e.to_string(), "bad\n --> <input>, column 6");
+    }
+
+    #[test]
+    fn test_chains_source() {
+        let e: Error = "x".parse::<u8>().unwrap_err().into();
+        assert_eq!(e.kind(), ErrorKind::Parse);
+        assert!(error::Error::source(&e).is_some());
+        assert_eq!(e.to_string(), "invalid integer: invalid digit found in string");
     }
 }
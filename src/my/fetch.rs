@@ -0,0 +1,163 @@
+//! Downloads puzzle inputs that are not on disk yet, see [Fetcher].
+
+use crate::config::Config;
+use crate::http::Request;
+use crate::input::{InputReader, Inputs};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Downloads inputs into an inputs root, which doubles as the cache.
+pub struct Fetcher {
+    config: Config,
+    inputs: Inputs,
+}
+
+impl Fetcher {
+    pub fn new(config: Config, inputs: Inputs) -> Self {
+        Self { config, inputs }
+    }
+
+    /// Returns the path of `day`'s input, downloading it first if it is not there.
+    ///
+    /// An input on disk is never downloaded again, delete it to fetch a fresh copy. Empty files,
+    /// like the placeholders [crate::scaffold::new_day] creates, do not count.
+    pub fn fetch(&self, day: u8) -> crate::Result<PathBuf> {
+        let path = self.inputs.path(day);
+        if is_cached(&path) {
+            return Ok(path);
+        }
+
+        let url = self
+            .config
+            .base_url()?
+            .join(&format!("{}/day/{}/input", self.inputs.year(), day));
+        let session = self.config.session(self.inputs.profile())?;
+        let response = Request::get(url.clone())
+            .with_header("Cookie", &format!("session={}", session))
+            .send()?;
+        if !response.is_success() {
+            let reason = response.body.lines().next().unwrap_or("").trim();
+            return Err(crate::Error::io(format!(
+                "downloading day {} from {} failed with status {}: {}",
+                day, url, response.status, reason
+            )));
+        }
+
+        // Write to the side first so an interrupted download never looks cached
+        let partial = path.with_extension("part");
+        let written = fs::create_dir_all(self.inputs.dir())
+            .and_then(|_| fs::write(&partial, &response.body))
+            .and_then(|_| fs::rename(&partial, &path));
+        if let Err(e) = written {
+            return Err(
+                crate::Error::io(format!("could not save {}", path.display())).with_source(e),
+            );
+        }
+        return Ok(path);
+    }
+
+    pub fn reader(&self, day: u8) -> crate::Result<InputReader> {
+        InputReader::from_path(self.fetch(day)?)
+    }
+}
+
+/// Opens `day`'s input from `inputs`, downloading it when it is missing.
+///
+/// The site is only contacted, and [Config] only read, when the input is not on disk.
+pub fn open(inputs: &Inputs, day: u8) -> crate::Result<InputReader> {
+    if is_cached(&inputs.path(day)) {
+        return inputs.reader(day);
+    }
+    let config = Config::from_env()?;
+    if let Err(e) = config.session(inputs.profile()) {
+        return Err(crate::Error::validation(format!(
+            "{} is missing and can not be downloaded",
+            inputs.path(day).display()
+        ))
+        .with_source(e));
+    }
+    return Fetcher::new(config, inputs.clone()).reader(day);
+}
+
+fn is_cached(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|metadata| metadata.len() != 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::mock;
+    use std::env;
+
+    fn scratch(name: &str) -> Inputs {
+        let root = env::temp_dir().join(format!("aoc-fetch-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        return Inputs::new(root);
+    }
+
+    fn config(base_url: String) -> Config {
+        Config {
+            base_url,
+            session: Some(String::from("cookie")),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn test_downloads_once_then_uses_the_cache() {
+        let (url, server) = mock::serve(vec![mock::response(200, "1721\n979\n")]);
+        let inputs = scratch("cache");
+        let fetcher = Fetcher::new(config(url), inputs.clone());
+
+        let path = fetcher.fetch(1).unwrap();
+        assert_eq!(fs::read_to_string(&path).unwrap(), "1721\n979\n");
+        // The server only answers once, a second download would fail
+        assert_eq!(fetcher.fetch(1).unwrap(), path);
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 1);
+        assert!(requests[0].starts_with("GET /2020/day/1/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=cookie\r\n"));
+        fs::remove_dir_all(inputs.root()).unwrap();
+    }
+
+    #[test]
+    fn test_failed_downloads_are_not_cached() {
+        let (url, server) =
+            mock::serve(vec![mock::response(400, "Puzzle inputs differ by user.\n")]);
+        let inputs = scratch("failure");
+        let fetcher = Fetcher::new(config(url), inputs.clone());
+
+        let e = fetcher.fetch(2).unwrap_err();
+        assert_eq!(e.kind(), crate::ErrorKind::Io);
+        assert!(e
+            .to_string()
+            .contains("status 400: Puzzle inputs differ by user."));
+        assert!(!inputs.path(2).exists());
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn test_downloads_into_the_profile() {
+        let (url, server) = mock::serve(vec![mock::response(200, "abc\n")]);
+        let inputs = scratch("profile").with_profile("alice").unwrap().with_year(2019);
+        let mut config = config(url);
+        config.sessions.insert(String::from("alice"), String::from("hers"));
+
+        let path = Fetcher::new(config, inputs.clone()).fetch(6).unwrap();
+        assert_eq!(path, inputs.root().join("alice/2019/day_6"));
+        let requests = server.join().unwrap();
+        assert!(requests[0].starts_with("GET /2019/day/6/input HTTP/1.1\r\n"));
+        assert!(requests[0].contains("Cookie: session=hers\r\n"));
+        fs::remove_dir_all(inputs.root()).unwrap();
+    }
+
+    #[test]
+    fn test_needs_a_session() {
+        let fetcher = Fetcher::new(Config::default(), scratch("session"));
+        assert_eq!(
+            fetcher.fetch(3).unwrap_err().kind(),
+            crate::ErrorKind::Validation
+        );
+    }
+}
@@ -0,0 +1,439 @@
+//! Synthetic puzzle inputs with answers known by construction, for stress and scale testing.
+//!
+//! Every day has a [Generator] that builds an input from a seeded [Rng], so the same seed always
+//! gives the same input. Inputs can also be made deliberately invalid, in which case the expected
+//! outcome is the kind of error solving should fail with.
+//!
+//! Generators also [anonymize] real inputs, which can not be shared, into random inputs of the same
+//! structure that can.
+
+use crate::days;
+use crate::input::InputReader;
+use crate::solution::{Answer, Part};
+use crate::ErrorKind;
+use std::fmt;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+pub mod day4;
+pub mod day5;
+pub mod day6;
+
+/// A small, fast, seedable pseudo random number generator (SplitMix64).
+///
+/// Not suitable for anything but test data.
+#[derive(Clone, Debug)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        return z ^ (z >> 31);
+    }
+
+    /// A number in `0..n`, `n` must not be 0.
+    pub fn below(&mut self, n: u64) -> u64 {
+        // Multiply-shift keeps the bias negligible for the ranges used here
+        ((self.next_u64() as u128 * n as u128) >> 64) as u64
+    }
+
+    /// A number in `low..high`, the range must not be empty.
+    pub fn range(&mut self, low: u64, high: u64) -> u64 {
+        low + self.below(high - low)
+    }
+
+    /// True with probability `numerator / denominator`.
+    pub fn chance(&mut self, numerator: u64, denominator: u64) -> bool {
+        self.below(denominator) < numerator
+    }
+
+    pub fn choose<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        &items[self.below(items.len() as u64) as usize]
+    }
+
+    /// Fisher-Yates shuffles `items` in place.
+    pub fn shuffle<T>(&mut self, items: &mut [T]) {
+        for i in (1..items.len()).rev() {
+            let j = self.below(i as u64 + 1) as usize;
+            items.swap(i, j);
+        }
+    }
+}
+
+/// What solving a part of a generated input should produce.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Expected {
+    Answer(Answer),
+    /// Solving should fail with an error of this kind.
+    Error(ErrorKind),
+}
+
+impl fmt::Display for Expected {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Expected::Answer(answer) => write!(f, "{}", answer),
+            Expected::Error(kind) => write!(f, "{} error", kind.name()),
+        }
+    }
+}
+
+/// A generated input and what solving it should produce.
+#[derive(Clone, Debug)]
+pub struct Generated {
+    pub text: String,
+    pub part_one: Expected,
+    pub part_two: Expected,
+}
+
+impl Generated {
+    pub fn expected(&self, part: Part) -> &Expected {
+        match part {
+            Part::One => &self.part_one,
+            Part::Two => &self.part_two,
+        }
+    }
+
+    /// An input both parts should fail on with `kind`.
+    fn failing(text: String, kind: ErrorKind) -> Self {
+        Self {
+            text,
+            part_one: Expected::Error(kind),
+            part_two: Expected::Error(kind),
+        }
+    }
+}
+
+/// Knobs for a [Generator], each day uses the ones that make sense for it.
+#[derive(Clone, Debug)]
+pub struct Params {
+    /// Number of entries, e.g. lines, passports, rows or groups.
+    pub size: usize,
+    /// Width of day 3's map.
+    pub width: Option<usize>,
+    /// The free seat id of day 5.
+    pub seat: Option<u64>,
+    /// Plant a defect the solver should reject.
+    pub invalid: bool,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            size: 200,
+            width: None,
+            seat: None,
+            invalid: false,
+        }
+    }
+}
+
+/// Builds inputs for one day.
+pub trait Generator: Sync {
+    fn day(&self) -> u8;
+
+    /// A new input drawn from `rng`, sizes below the day's minimum are raised to it.
+    fn generate(&self, rng: &mut Rng, params: &Params) -> Generated;
+
+    /// Re-randomizes `text`, a valid input, keeping its shape: sizes, the mix of entries that pass
+    /// and fail, densities and gaps. Answers generally change.
+    fn anonymize(&self, rng: &mut Rng, text: &str) -> crate::Result<String>;
+
+    /// Whether [Params::invalid] does anything, some days accept every input of the right shape.
+    fn can_invalidate(&self) -> bool {
+        true
+    }
+}
+
+static GENERATORS: [&dyn Generator; 6] = [
+    &day1::Generator1,
+    &day2::Generator2,
+    &day3::Generator3,
+    &day4::Generator4,
+    &day5::Generator5,
+    &day6::Generator6,
+];
+
+/// Every day that has a generator, in calendar order.
+pub fn all() -> &'static [&'static dyn Generator] {
+    &GENERATORS
+}
+
+/// Looks up the generator for `day`, or None if it has none.
+pub fn get(day: u8) -> Option<&'static dyn Generator> {
+    GENERATORS.iter().copied().find(|g| g.day() == day)
+}
+
+/// Generates `day`'s input for `seed`, or None if the day has no generator.
+pub fn generate(day: u8, seed: u64, params: &Params) -> Option<Generated> {
+    let generator = get(day)?;
+    return Some(generator.generate(&mut Rng::new(seed), params));
+}
+
+/// Anonymizes `text`, an input for `day`, with `seed` and solves the result for its answers.
+pub fn anonymize(day: u8, seed: u64, text: &str) -> crate::Result<Generated> {
+    let generator = match get(day) {
+        Some(generator) => generator,
+        None => {
+            return Err(crate::Error::validation(format!(
+                "day {} has no input generator",
+                day
+            )))
+        }
+    };
+    let text = generator.anonymize(&mut Rng::new(seed), text)?;
+    return Ok(solve(day, text));
+}
+
+/// What solving `text` with `day`'s solver produces, for inputs whose answers are not known by
+/// construction.
+pub fn solve(day: u8, text: String) -> Generated {
+    let puzzle = days::get(day).expect("every generated day has a solver");
+    let reader = InputReader::from_text(text.clone(), String::from("generated input"));
+    let input = match puzzle.parse(reader) {
+        Ok(input) => input,
+        Err(e) => return Generated::failing(text, e.kind()),
+    };
+    let expected = |part| match puzzle.solve(input.as_ref(), part) {
+        Ok(answer) => Expected::Answer(answer),
+        Err(e) => Expected::Error(e.kind()),
+    };
+    return Generated {
+        part_one: expected(Part::One),
+        part_two: expected(Part::Two),
+        text,
+    };
+}
+
+/// Reads `text` with `parse`, for anonymizers to make sense of an input.
+fn parse<T>(text: &str, parse: fn(InputReader) -> crate::Result<T>) -> crate::Result<T> {
+    parse(InputReader::from_text(
+        String::from(text),
+        String::from("input to anonymize"),
+    ))
+}
+
+/// Joins `lines` into newline terminated text.
+fn lines<S: AsRef<str>>(lines: &[S]) -> String {
+    let mut text = String::new();
+    for line in lines {
+        text.push_str(line.as_ref());
+        text.push('\n');
+    }
+    return text;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Solves `generated` with the day's solver and checks every part it has solved.
+    fn check(day: u8, seed: u64, generated: &Generated) {
+        let actual = solve(day, generated.text.clone());
+        for part in Part::ALL.iter().copied() {
+            if *actual.expected(part) == Expected::Answer(Answer::Unsolved) {
+                continue;
+            }
+            assert_eq!(
+                actual.expected(part),
+                generated.expected(part),
+                "day {} part {} seed {}:\n{}",
+                day,
+                part,
+                seed,
+                generated.text
+            );
+        }
+    }
+
+    #[test]
+    fn test_rng_is_deterministic() {
+        let (mut a, mut b) = (Rng::new(7), Rng::new(7));
+        for _ in 0..100 {
+            assert_eq!(a.next_u64(), b.next_u64());
+        }
+        let mut rng = Rng::new(1);
+        assert!((0..1000).all(|_| rng.range(5, 9) >= 5 && rng.range(5, 9) < 9));
+    }
+
+    #[test]
+    fn test_generated_inputs_solve_to_their_answers() {
+        for generator in all() {
+            for seed in 0..20 {
+                let params = Params {
+                    size: 1 + seed as usize * 7,
+                    ..Params::default()
+                };
+                let generated = generator.generate(&mut Rng::new(seed), &params);
+                check(generator.day(), seed, &generated);
+            }
+        }
+    }
+
+    #[test]
+    fn test_invalid_inputs_fail() {
+        for generator in all().iter().filter(|g| g.can_invalidate()) {
+            for seed in 0..20 {
+                let params = Params {
+                    size: 1 + seed as usize * 7,
+                    invalid: true,
+                    ..Params::default()
+                };
+                let generated = generator.generate(&mut Rng::new(seed), &params);
+                assert!(Part::ALL
+                    .iter()
+                    .any(|&part| matches!(generated.expected(part), Expected::Error(_))));
+                check(generator.day(), seed, &generated);
+            }
+        }
+    }
+
+    #[test]
+    fn test_some_toboggan_slopes_miss_every_tree() {
+        let params = Params::default();
+        let products: Vec<Expected> = (0..20)
+            .map(|seed| generate(3, seed, &params).unwrap().part_two)
+            .collect();
+        let zero = Expected::Answer(Answer::from(0u64));
+        assert!(products.contains(&zero));
+        assert!(products.iter().any(|product| *product != zero));
+    }
+
+    #[test]
+    fn test_large_toboggan_maps_overflow_part_two() {
+        let params = Params {
+            size: 40_000,
+            width: Some(31),
+            ..Params::default()
+        };
+        for seed in [2, 5].iter().copied() {
+            let generated = generate(3, seed, &params).unwrap();
+            assert!(matches!(generated.part_one, Expected::Answer(_)));
+            assert_eq!(generated.part_two, Expected::Error(ErrorKind::Invariant));
+            check(3, seed, &generated);
+        }
+    }
+
+    #[test]
+    fn test_chosen_free_seat() {
+        for seat in [1, 100, 1022].iter().copied() {
+            let params = Params {
+                size: 40,
+                seat: Some(seat),
+                ..Params::default()
+            };
+            let generated = generate(5, seat, &params).unwrap();
+            assert_eq!(generated.part_two, Expected::Answer(Answer::from(seat)));
+            check(5, seat, &generated);
+        }
+    }
+
+    #[test]
+    fn test_anonymize_keeps_structure() {
+        let params = Params::default();
+        for generator in all() {
+            let day = generator.day();
+            let original = generator.generate(&mut Rng::new(3), &params);
+            let anonymized = anonymize(day, 4, &original.text).unwrap();
+            assert_ne!(anonymized.text, original.text, "day {}", day);
+            assert_eq!(
+                anonymized.text.lines().count(),
+                original.text.lines().count()
+            );
+
+            match day {
+                1 => assert!(matches!(anonymized.part_two, Expected::Answer(_))),
+                3 => {
+                    let trees = |text: &str| text.matches('#').count();
+                    assert_eq!(trees(&anonymized.text), trees(&original.text));
+                    assert_toboggan_answers(&anonymized);
+
+                    // A lone tree leaves most slopes without any
+                    let sparse = format!("#{}\n", ".".repeat(30)).repeat(40);
+                    for seed in 0..10 {
+                        assert_toboggan_answers(&anonymize(day, seed, &sparse).unwrap());
+                    }
+                }
+                5 => {
+                    let (original, anonymized) = match (
+                        &original.part_one,
+                        &original.part_two,
+                        &anonymized.part_one,
+                        &anonymized.part_two,
+                    ) {
+                        (
+                            Expected::Answer(Answer::UInt(a)),
+                            Expected::Answer(Answer::UInt(b)),
+                            Expected::Answer(Answer::UInt(c)),
+                            Expected::Answer(Answer::UInt(d)),
+                        ) => (a - b, c - d),
+                        other => panic!("unexpected answers {:?}", other),
+                    };
+                    assert_eq!(anonymized, original);
+                }
+                _ => check(
+                    day,
+                    4,
+                    &Generated {
+                        text: anonymized.text,
+                        ..original
+                    },
+                ),
+            }
+        }
+    }
+
+    /// Checks the answers reported for a day 3 map against counting its trees by hand.
+    fn assert_toboggan_answers(generated: &Generated) {
+        let rows: Vec<&[u8]> = generated.text.lines().map(str::as_bytes).collect();
+        let strikes: Vec<u64> = [(1, 1), (1, 3), (1, 5), (1, 7), (2, 1)]
+            .iter()
+            .map(|&(down, right): &(usize, usize)| {
+                (0..rows.len())
+                    .step_by(down)
+                    .filter(|&y| rows[y][(y / down * right) % rows[y].len()] == b'#')
+                    .count() as u64
+            })
+            .collect();
+        let product = strikes
+            .iter()
+            .try_fold(1u64, |product, &strikes| product.checked_mul(strikes));
+        let part_two = match product {
+            Some(product) => Expected::Answer(Answer::from(product)),
+            None => Expected::Error(ErrorKind::Invariant),
+        };
+        assert_eq!(
+            generated.part_one,
+            Expected::Answer(Answer::from(strikes[1])),
+            "{}",
+            generated.text
+        );
+        assert_eq!(generated.part_two, part_two, "{}", generated.text);
+    }
+
+    #[test]
+    fn test_anonymize_rejects_invalid_input() {
+        let e = anonymize(5, 1, "FFFFFFFLLX\n").unwrap_err();
+        assert_eq!(e.kind(), ErrorKind::Parse);
+        assert!(anonymize(25, 1, "").is_err());
+    }
+
+    #[test]
+    fn test_same_seed_same_input() {
+        let params = Params::default();
+        for generator in all() {
+            let a = generator.generate(&mut Rng::new(42), &params);
+            let b = generator.generate(&mut Rng::new(42), &params);
+            assert_eq!(a.text, b.text);
+        }
+    }
+}
@@ -0,0 +1,139 @@
+//! Expense reports with exactly one pair and one triple of entries summing to 2020.
+
+use super::{lines, Expected, Generated, Generator, Params, Rng};
+use crate::days::day1::{self, InputType, SHITHOLE_YEAR};
+use crate::solution::Answer;
+use crate::ErrorKind;
+use std::collections::{BTreeSet, HashSet};
+
+/// Filler entries are at least this big, so no two of them fit in [SHITHOLE_YEAR] together.
+const FILLER_MIN: InputType = SHITHOLE_YEAR * 2 / 3;
+
+/// Draws from the input's range before falling back to filler that can not be part of a sum.
+const ATTEMPTS: usize = 1000;
+
+pub struct Generator1;
+
+impl Generator for Generator1 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    /// Plants a pair and a triple, the rest is filler that can not complete either.
+    ///
+    /// Invalid reports either have a line that is not a number or lack the triple.
+    fn generate(&self, rng: &mut Rng, params: &Params) -> Generated {
+        let (pair, triple) = plant(rng);
+        let mut planted: Vec<InputType> = pair.iter().chain(triple.iter()).copied().collect();
+
+        // Filler must not be the missing piece of any sum of planted entries
+        let mut forbidden = BTreeSet::new();
+        for (i, x) in planted.iter().enumerate() {
+            forbidden.insert(SHITHOLE_YEAR - x);
+            for y in planted[i + 1..].iter() {
+                forbidden.insert(SHITHOLE_YEAR - x - y);
+            }
+        }
+
+        let mut part_two = Expected::Answer(Answer::from(triple.iter().product::<InputType>()));
+        if params.invalid && rng.chance(1, 2) {
+            planted.truncate(pair.len());
+            part_two = Expected::Error(ErrorKind::Invariant);
+        }
+
+        let mut entries: Vec<String> = planted.iter().map(InputType::to_string).collect();
+        while entries.len() < params.size.max(5) {
+            let filler = rng.range(FILLER_MIN as u64, SHITHOLE_YEAR as u64) as InputType;
+            if !forbidden.contains(&filler) {
+                entries.push(filler.to_string());
+            }
+        }
+        rng.shuffle(&mut entries);
+
+        if params.invalid && part_two != Expected::Error(ErrorKind::Invariant) {
+            let line = rng.below(entries.len() as u64) as usize;
+            entries[line].push('x');
+            return Generated::failing(lines(&entries), ErrorKind::Parse);
+        }
+
+        return Generated {
+            text: lines(&entries),
+            part_one: Expected::Answer(Answer::from(pair[0] * pair[1])),
+            part_two,
+        };
+    }
+
+    /// Keeps the number of entries, whether there is a pair and a triple, and the range of the
+    /// other entries.
+    fn anonymize(&self, rng: &mut Rng, text: &str) -> crate::Result<String> {
+        let entries = super::parse(text, day1::collect_inputs)?;
+        let (low, high) = match (entries.iter().min(), entries.iter().max()) {
+            (Some(&low), Some(&high)) => (low as i64, high as i64 + 1),
+            _ => return Ok(String::new()),
+        };
+
+        let (pair, triple) = plant(rng);
+        let mut planted = Vec::new();
+        if count_sums(&entries, 2, SHITHOLE_YEAR) > 0 {
+            planted.extend(pair.iter().copied());
+        }
+        if count_sums(&entries, 3, SHITHOLE_YEAR) > 0 {
+            planted.extend(triple.iter().copied());
+        }
+
+        // Filler must not complete a pair with one entry so far or a triple with two
+        let mut anonymized = planted;
+        let mut sums = HashSet::new();
+        for (i, entry) in anonymized.iter().enumerate() {
+            sums.extend(anonymized[i + 1..].iter().map(|other| entry + other));
+        }
+        let mut attempts = 0;
+        while anonymized.len() < entries.len() {
+            let filler = match attempts < ATTEMPTS {
+                true => (low + rng.below((high - low) as u64) as i64) as InputType,
+                false => rng.range(FILLER_MIN as u64, SHITHOLE_YEAR as u64) as InputType,
+            };
+            let missing = SHITHOLE_YEAR - filler;
+            if anonymized.contains(&missing) || sums.contains(&missing) {
+                attempts += 1;
+                continue;
+            }
+            sums.extend(anonymized.iter().map(|other| filler + other));
+            anonymized.push(filler);
+            attempts = 0;
+        }
+        rng.shuffle(&mut anonymized);
+
+        let entries: Vec<String> = anonymized.iter().map(InputType::to_string).collect();
+        return Ok(lines(&entries));
+    }
+}
+
+/// Draws a pair and a triple summing to [SHITHOLE_YEAR] that are the only such pair and triple
+/// among the five entries.
+fn plant(rng: &mut Rng) -> ([InputType; 2], [InputType; 3]) {
+    let year = SHITHOLE_YEAR as u64;
+    loop {
+        // Half the year is left out so the pair is two distinct entries
+        let a = rng.range(1, year / 2) as InputType;
+        let c = rng.range(1, year / 3) as InputType;
+        let d = rng.range(c as u64 + 1, (year - c as u64).div_ceil(2)) as InputType;
+        let pair = [a, SHITHOLE_YEAR - a];
+        let triple = [c, d, SHITHOLE_YEAR - c - d];
+
+        let all: Vec<InputType> = pair.iter().chain(triple.iter()).copied().collect();
+        if count_sums(&all, 2, SHITHOLE_YEAR) == 1 && count_sums(&all, 3, SHITHOLE_YEAR) == 1 {
+            return (pair, triple);
+        }
+    }
+}
+
+/// The number of `k` entry combinations of `entries` summing to `target`.
+fn count_sums(entries: &[InputType], k: usize, target: InputType) -> usize {
+    if k == 0 {
+        return (target == 0) as usize;
+    }
+    return (0..entries.len())
+        .map(|i| count_sums(&entries[i + 1..], k - 1, target - entries[i]))
+        .sum();
+}
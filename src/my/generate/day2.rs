@@ -0,0 +1,154 @@
+//! Password databases where each password is built to pass or fail each policy on purpose.
+
+use super::{lines, Expected, Generated, Generator, Params, Rng};
+use crate::days::day2;
+use crate::solution::Answer;
+use crate::{Error, ErrorKind};
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+pub struct Generator2;
+
+impl Generator for Generator2 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    /// Invalid databases have a line without the `: ` between rule and password.
+    fn generate(&self, rng: &mut Rng, params: &Params) -> Generated {
+        let mut entries = Vec::new();
+        let (mut count_valid, mut position_valid) = (0u64, 0u64);
+        for _ in 0..params.size.max(1) {
+            let (entry, by_count, by_position) = entry(rng);
+            entries.push(entry);
+            count_valid += by_count as u64;
+            position_valid += by_position as u64;
+        }
+
+        if params.invalid {
+            let line = rng.below(entries.len() as u64) as usize;
+            entries[line] = entries[line].replacen(": ", " ", 1);
+            return Generated::failing(lines(&entries), ErrorKind::Parse);
+        }
+
+        return Generated {
+            text: lines(&entries),
+            part_one: Expected::Answer(Answer::from(count_valid)),
+            part_two: Expected::Answer(Answer::from(position_valid)),
+        };
+    }
+
+    /// Keeps every rule's positions, every password's length and where the rule's character is at
+    /// the rule's positions and how often it is elsewhere, so each entry passes the same policies.
+    fn anonymize(&self, rng: &mut Rng, text: &str) -> crate::Result<String> {
+        let mut entries = Vec::new();
+        let database = super::parse(text, day2::parse)?;
+        for entry in database.entries() {
+            let (rule_character, positions) = match entry.rule().simple() {
+                Some(simple) => simple,
+                None => {
+                    return Err(Error::validation(format!(
+                        "only '1-3 a' rules can be anonymized, not '{}'",
+                        entry.rule()
+                    )))
+                }
+            };
+            let character = *rng.choose(LETTERS);
+            let original: Vec<char> = entry.password().chars().collect();
+            let mut password: Vec<u8> = (0..original.len())
+                .map(|_| other_letter(rng, character))
+                .collect();
+
+            // The rule's positions keep the character where they had it, the rest get it as often
+            let at_rule = |i: &usize| positions.contains(&(i + 1));
+            for i in (0..original.len()).filter(at_rule) {
+                if original[i] == rule_character {
+                    password[i] = character;
+                }
+            }
+            let mut others: Vec<usize> = (0..original.len()).filter(|i| !at_rule(i)).collect();
+            let elsewhere = others
+                .iter()
+                .filter(|&&i| original[i] == rule_character)
+                .count();
+            rng.shuffle(&mut others);
+            for &i in others.iter().take(elsewhere) {
+                password[i] = character;
+            }
+
+            let positions: Vec<String> = positions.iter().map(usize::to_string).collect();
+            entries.push(format!(
+                "{} {}: {}",
+                positions.join("-"),
+                character as char,
+                String::from_utf8(password).expect("passwords are ASCII")
+            ));
+        }
+        return Ok(lines(&entries));
+    }
+}
+
+/// A `low-high c: password` line, with whether it is valid by count and by position.
+fn entry(rng: &mut Rng) -> (String, bool, bool) {
+    let low = rng.range(1, 10) as usize;
+    let high = rng.range(low as u64 + 1, low as u64 + 10) as usize;
+    let len = rng.range(high as u64, high as u64 + 8) as usize;
+    let character = *rng.choose(LETTERS);
+
+    // The rule's positions hold none, one or both of the occurrences
+    let by_position = rng.chance(1, 2);
+    let at_positions = match by_position {
+        true => 1,
+        false => 2 * rng.below(2) as usize,
+    };
+
+    // Pick a total occurrence count, the other len - 2 places hold the rest
+    let mut by_count = rng.chance(1, 2);
+    let totals = |by_count: bool| -> Vec<usize> {
+        (at_positions..=at_positions + len - 2)
+            .filter(|total| (low..=high).contains(total) == by_count)
+            .collect()
+    };
+    let mut candidates = totals(by_count);
+    if candidates.is_empty() {
+        by_count = !by_count;
+        candidates = totals(by_count);
+    }
+    let total = *rng.choose(&candidates);
+
+    let mut password: Vec<u8> = (0..len).map(|_| other_letter(rng, character)).collect();
+    let (first, second) = (low - 1, high - 1);
+    match at_positions {
+        1 if rng.chance(1, 2) => password[first] = character,
+        1 => password[second] = character,
+        2 => {
+            password[first] = character;
+            password[second] = character;
+        }
+        _ => (),
+    }
+    let mut others: Vec<usize> = (0..len).filter(|&i| i != first && i != second).collect();
+    rng.shuffle(&mut others);
+    for &i in others.iter().take(total - at_positions) {
+        password[i] = character;
+    }
+
+    let line = format!(
+        "{}-{} {}: {}",
+        low,
+        high,
+        character as char,
+        String::from_utf8(password).expect("passwords are ASCII")
+    );
+    return (line, by_count, by_position);
+}
+
+/// A random letter that is not `character`.
+fn other_letter(rng: &mut Rng, character: u8) -> u8 {
+    loop {
+        let other = *rng.choose(LETTERS);
+        if other != character {
+            return other;
+        }
+    }
+}
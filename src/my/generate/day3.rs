@@ -0,0 +1,113 @@
+//! Toboggan maps of any width and height.
+
+use super::{lines, Expected, Generated, Generator, Params, Rng};
+use crate::days::day3::{self, Coordinate, Landmark};
+use crate::solution::Answer;
+use crate::ErrorKind;
+
+/// The width of the maps handed out by the North Pole.
+const DEFAULT_WIDTH: usize = 31;
+
+/// The surveyed slopes as (down, right), part one only takes the second.
+const SLOPES: [(usize, usize); 5] = [(1, 1), (1, 3), (1, 5), (1, 7), (2, 1)];
+
+pub struct Generator3;
+
+impl Generator for Generator3 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    /// Maps are [Params::size] rows of [Params::width], some with a slope that hits no trees.
+    ///
+    /// Invalid maps have an unknown landmark or a row of the wrong width.
+    fn generate(&self, rng: &mut Rng, params: &Params) -> Generated {
+        let width = params.width.unwrap_or(DEFAULT_WIDTH).max(1);
+        let height = params.size.max(3);
+        let density = rng.range(10, 40);
+        let mut map: Vec<Vec<u8>> = (0..height)
+            .map(|_| {
+                (0..width)
+                    .map(|_| if rng.chance(density, 100) { b'#' } else { b'.' })
+                    .collect()
+            })
+            .collect();
+
+        // Some maps have a slope that misses every tree, which makes the product zero
+        if rng.chance(1, 4) {
+            let &(down, right) = rng.choose(&SLOPES);
+            for y in (0..height).step_by(down) {
+                map[y][(y / down * right) % width] = b'.';
+            }
+        }
+        let mut strikes = [0u64; 5];
+        for (strikes, &(down, right)) in strikes.iter_mut().zip(SLOPES.iter()) {
+            *strikes = (0..height)
+                .step_by(down)
+                .filter(|&y| map[y][(y / down * right) % width] == b'#')
+                .count() as u64;
+        }
+
+        if params.invalid {
+            let row = rng.below(height as u64) as usize;
+            if width > 1 && rng.chance(1, 2) {
+                map[row].pop();
+                return Generated::failing(rows(&map), ErrorKind::Validation);
+            }
+            let column = rng.below(width as u64) as usize;
+            map[row][column] = b'O';
+            return Generated::failing(rows(&map), ErrorKind::Parse);
+        }
+
+        return Generated {
+            text: rows(&map),
+            part_one: Expected::Answer(Answer::from(strikes[1])),
+            part_two: product(&strikes),
+        };
+    }
+
+    /// Keeps the width, height and number of trees, scattering the trees anew.
+    fn anonymize(&self, rng: &mut Rng, text: &str) -> crate::Result<String> {
+        let map = super::parse(text, day3::parse)?;
+        let (width, height) = (map.width(), map.height());
+        if width == 0 {
+            return Ok(String::new());
+        }
+        let mut cells: Vec<u8> = Vec::with_capacity(width * height);
+        for y in 0..height {
+            for x in 0..width {
+                let coordinate = Coordinate {
+                    x: x as u32,
+                    y: y as u32,
+                };
+                cells.push(match map.get(coordinate) {
+                    Some(Landmark::Tree) => b'#',
+                    _ => b'.',
+                });
+            }
+        }
+        rng.shuffle(&mut cells);
+
+        let map: Vec<Vec<u8>> = cells.chunks(width).map(<[u8]>::to_vec).collect();
+        return Ok(rows(&map));
+    }
+}
+
+fn rows(map: &[Vec<u8>]) -> String {
+    let rows: Vec<&str> = map
+        .iter()
+        .map(|row| std::str::from_utf8(row).expect("maps are ASCII"))
+        .collect();
+    return lines(&rows);
+}
+
+/// The product of the slopes' strikes, which large maps overflow.
+fn product(strikes: &[u64]) -> Expected {
+    match strikes
+        .iter()
+        .try_fold(1u64, |product, &strikes| product.checked_mul(strikes))
+    {
+        Some(product) => Expected::Answer(Answer::from(product)),
+        None => Expected::Error(ErrorKind::Invariant),
+    }
+}
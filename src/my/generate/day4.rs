@@ -0,0 +1,182 @@
+//! Passport batches mixing valid, incomplete and complete but invalid passports.
+
+use super::{Expected, Generated, Generator, Params, Rng};
+use crate::days::day4::{self, PassportValidator};
+use crate::input::InputReader;
+use crate::solution::Answer;
+use crate::ErrorKind;
+
+const REQUIRED_FIELDS: [&str; 7] = ["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid"];
+const EYE_COLORS: [&str; 7] = ["amb", "blu", "brn", "gry", "grn", "hzl", "oth"];
+const HEX: &[u8] = b"0123456789abcdef";
+
+pub struct Generator4;
+
+impl Generator for Generator4 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    /// Invalid batches have a passport with a field that is not a passport field.
+    fn generate(&self, rng: &mut Rng, params: &Params) -> Generated {
+        let mut passports = Vec::new();
+        let (mut complete, mut valid) = (0u64, 0u64);
+        for _ in 0..params.size.max(1) {
+            let mut fields: Vec<(&str, String)> = REQUIRED_FIELDS
+                .iter()
+                .map(|&key| (key, valid_value(rng, key)))
+                .collect();
+            if rng.chance(1, 2) {
+                fields.push(("cid", rng.range(100, 1000).to_string()));
+            }
+
+            // Valid, complete with one invalid field, or missing a field
+            let i = rng.below(REQUIRED_FIELDS.len() as u64) as usize;
+            match rng.below(3) {
+                0 => {
+                    complete += 1;
+                    valid += 1;
+                }
+                1 => {
+                    complete += 1;
+                    fields[i].1 = invalid_value(rng, fields[i].0);
+                }
+                _ => {
+                    fields.remove(i);
+                }
+            }
+            passports.push(fields);
+        }
+
+        if params.invalid {
+            let i = rng.below(passports.len() as u64) as usize;
+            passports[i].push(("hat", String::from("red")));
+        }
+
+        let mut text = String::new();
+        for (i, fields) in passports.iter_mut().enumerate() {
+            rng.shuffle(fields);
+            if i > 0 {
+                text.push('\n');
+            }
+            for (j, (key, value)) in fields.iter().enumerate() {
+                if j > 0 {
+                    text.push(if rng.chance(1, 4) { '\n' } else { ' ' });
+                }
+                text.push_str(&format!("{}:{}", key, value));
+            }
+            text.push('\n');
+        }
+
+        if params.invalid {
+            return Generated::failing(text, ErrorKind::Validation);
+        }
+        return Generated {
+            text,
+            part_one: Expected::Answer(Answer::from(complete)),
+            part_two: Expected::Answer(Answer::from(valid)),
+        };
+    }
+
+    /// Keeps which fields each passport has, which of them are valid and how many fields are on
+    /// each line, drawing new values and a new field order.
+    fn anonymize(&self, rng: &mut Rng, text: &str) -> crate::Result<String> {
+        let passports = super::parse(text, day4::parse)?;
+        let records = InputReader::from_text(String::from(text), String::new()).records();
+
+        let mut anonymized = Vec::new();
+        for (passport, record) in passports.iter().zip(records) {
+            let validator = PassportValidator::new(passport);
+            let mut fields: Vec<String> = passport
+                .keys()
+                .map(|key| {
+                    let value = match key.as_str() {
+                        "cid" => rng.range(100, 1000).to_string(),
+                        key if check(&validator, key) => valid_value(rng, key),
+                        key => invalid_value(rng, key),
+                    };
+                    format!("{}:{}", key, value)
+                })
+                .collect();
+            // HashMap order depends on the process, sort so the seed alone decides the shuffle
+            fields.sort();
+            rng.shuffle(&mut fields);
+
+            let mut fields = fields.into_iter();
+            let mut lines = Vec::new();
+            for line in record? {
+                let line: Vec<String> = fields
+                    .by_ref()
+                    .take(line.split_whitespace().count())
+                    .collect();
+                lines.push(line.join(" "));
+            }
+            anonymized.push(super::lines(&lines));
+        }
+        return Ok(anonymized.join("\n"));
+    }
+}
+
+/// Whether `key`'s value passes its rule.
+fn check(validator: &PassportValidator, key: &str) -> bool {
+    match key {
+        "byr" => validator.check_byr(),
+        "iyr" => validator.check_iyr(),
+        "eyr" => validator.check_eyr(),
+        "hgt" => validator.check_hgt(),
+        "hcl" => validator.check_hcl(),
+        "ecl" => validator.check_ecl(),
+        "pid" => validator.check_pid(),
+        _ => true,
+    }
+}
+
+/// A value passing the rules for `key`.
+fn valid_value(rng: &mut Rng, key: &str) -> String {
+    match key {
+        "byr" => rng.range(1920, 2003).to_string(),
+        "iyr" => rng.range(2010, 2021).to_string(),
+        "eyr" => rng.range(2020, 2031).to_string(),
+        "hgt" if rng.chance(1, 2) => format!("{}cm", rng.range(150, 194)),
+        "hgt" => format!("{}in", rng.range(59, 77)),
+        "hcl" => format!("#{}", hex(rng, 6)),
+        "ecl" => String::from(*rng.choose(&EYE_COLORS)),
+        "pid" => format!("{:09}", rng.below(1_000_000_000)),
+        _ => unreachable!("{} has no rules", key),
+    }
+}
+
+/// A value breaking the rules for `key`.
+fn invalid_value(rng: &mut Rng, key: &str) -> String {
+    let (below, above) = match key {
+        "byr" => ((1900, 1920), (2003, 2020)),
+        "iyr" => ((2000, 2010), (2021, 2030)),
+        "eyr" => ((2010, 2020), (2031, 2040)),
+        "hgt" => {
+            return match rng.below(3) {
+                0 if rng.chance(1, 2) => format!("{}cm", rng.range(100, 150)),
+                0 => format!("{}cm", rng.range(194, 250)),
+                1 if rng.chance(1, 2) => format!("{}in", rng.range(30, 59)),
+                1 => format!("{}in", rng.range(77, 99)),
+                _ => rng.range(59, 194).to_string(),
+            }
+        }
+        "hcl" => {
+            return match rng.below(3) {
+                0 => hex(rng, 6),
+                1 => format!("#{}", hex(rng, 5)),
+                _ => format!("#{}z", hex(rng, 5)),
+            }
+        }
+        "ecl" => return String::from(*rng.choose(&["red", "xry", "zzz", "gmt"])),
+        "pid" if rng.chance(1, 2) => return format!("{:08}", rng.below(100_000_000)),
+        "pid" => return format!("{:010}", rng.below(10_000_000_000)),
+        _ => unreachable!("{} has no rules", key),
+    };
+    let (low, high) = if rng.chance(1, 2) { below } else { above };
+    return rng.range(low, high).to_string();
+}
+
+fn hex(rng: &mut Rng, len: usize) -> String {
+    (0..len).map(|_| *rng.choose(HEX) as char).collect()
+}
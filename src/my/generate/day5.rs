@@ -0,0 +1,96 @@
+//! Boarding pass lists of a fully booked flight with a chosen free seat.
+
+use super::{lines, Expected, Generated, Generator, Params, Rng};
+use crate::days::day5::{self, Seat};
+use crate::solution::Answer;
+use crate::ErrorKind;
+
+/// The number of seat ids on the plane, 128 rows of 8.
+const SEATS: u64 = 128 * 8;
+
+pub struct Generator5;
+
+impl Generator for Generator5 {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    /// Books [Params::size] contiguous seats around the free [Params::seat], or a random one.
+    ///
+    /// Invalid lists have a mistyped boarding pass or a second free seat.
+    fn generate(&self, rng: &mut Rng, params: &Params) -> Generated {
+        // The free seat needs a booked neighbor on both sides
+        let booked = (params.size as u64).clamp(2, SEATS - 1);
+        let (first, free) = match params.seat {
+            Some(seat) => {
+                let free = seat.clamp(1, SEATS - 2);
+                let low = (free + 1).saturating_sub(booked);
+                let first = rng.range(low, free.min(SEATS - booked));
+                (first, free)
+            }
+            None => {
+                let first = rng.below(SEATS - booked);
+                (first, rng.range(first + 1, first + booked))
+            }
+        };
+        let last = first + booked;
+
+        let mut passes: Vec<String> = (first..=last)
+            .filter(|&id| id != free)
+            .map(boarding_pass)
+            .collect();
+
+        let mut part_two = Expected::Answer(Answer::from(free));
+        if params.invalid {
+            if rng.chance(1, 2) {
+                let mistyped = rng.below(passes.len() as u64) as usize;
+                passes[mistyped].replace_range(..1, "X");
+                rng.shuffle(&mut passes);
+                return Generated::failing(lines(&passes), ErrorKind::Parse);
+            }
+            // The seat before the free one is never the highest
+            let neighbor = boarding_pass(free - 1);
+            passes.retain(|pass| *pass != neighbor);
+            part_two = Expected::Error(ErrorKind::Invariant);
+        }
+        rng.shuffle(&mut passes);
+
+        return Generated {
+            text: lines(&passes),
+            part_one: Expected::Answer(Answer::from(last)),
+            part_two,
+        };
+    }
+
+    /// Moves every seat by the same random distance and shuffles the passes, keeping the gaps.
+    fn anonymize(&self, rng: &mut Rng, text: &str) -> crate::Result<String> {
+        let ids: Vec<u64> = super::parse(text, day5::parse)?
+            .iter()
+            .map(Seat::get_id)
+            .collect();
+        let (low, high) = match (ids.iter().min(), ids.iter().max()) {
+            (Some(&low), Some(&high)) => (low, high),
+            _ => return Ok(String::new()),
+        };
+
+        let first = rng.below(SEATS - (high - low));
+        let mut passes: Vec<String> = ids
+            .iter()
+            .map(|id| boarding_pass(id - low + first))
+            .collect();
+        rng.shuffle(&mut passes);
+        return Ok(lines(&passes));
+    }
+}
+
+/// Encodes `id` as 7 row letters of `F` and `B` followed by 3 column letters of `L` and `R`.
+fn boarding_pass(id: u64) -> String {
+    let (row, column) = (id / 8, id % 8);
+    let rows = (0..7)
+        .rev()
+        .map(|bit| if row >> bit & 1 == 1 { 'B' } else { 'F' });
+    let columns = (0..3)
+        .rev()
+        .map(|bit| if column >> bit & 1 == 1 { 'R' } else { 'L' });
+    return rows.chain(columns).collect();
+}
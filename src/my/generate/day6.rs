@@ -0,0 +1,92 @@
+//! Customs declaration groups.
+
+use super::{Expected, Generated, Generator, Params, Rng};
+use crate::days::day6;
+use crate::solution::Answer;
+
+pub struct Generator6;
+
+impl Generator for Generator6 {
+    fn day(&self) -> u8 {
+        6
+    }
+
+    /// Makes [Params::size] groups of one to five passengers, each answering from a shared pool so
+    /// groups have questions in common.
+    fn generate(&self, rng: &mut Rng, params: &Params) -> Generated {
+        let mut text = String::new();
+        let (mut anyone, mut everyone) = (0u64, 0u64);
+        for i in 0..params.size.max(1) {
+            // Questions as bits, 'a' is the lowest
+            let pool = loop {
+                let pool = rng.below(1 << 26) as u32;
+                if pool != 0 {
+                    break pool;
+                }
+            };
+            let passengers = rng.range(1, 6);
+            let (mut union, mut intersection) = (0u32, pool);
+            if i > 0 {
+                text.push('\n');
+            }
+            for _ in 0..passengers {
+                let answers = loop {
+                    let answers = rng.below(1 << 26) as u32 & pool;
+                    if answers != 0 {
+                        break answers;
+                    }
+                };
+                union |= answers;
+                intersection &= answers;
+
+                let mut questions: Vec<char> = (0..26u8)
+                    .filter(|bit| answers >> bit & 1 == 1)
+                    .map(|bit| (b'a' + bit) as char)
+                    .collect();
+                rng.shuffle(&mut questions);
+                text.extend(questions);
+                text.push('\n');
+            }
+            anyone += union.count_ones() as u64;
+            everyone += intersection.count_ones() as u64;
+        }
+
+        return Generated {
+            text,
+            part_one: Expected::Answer(Answer::from(anyone)),
+            part_two: Expected::Answer(Answer::from(everyone)),
+        };
+    }
+
+    /// Relabels the questions of each group and shuffles groups, passengers and answers, which
+    /// keeps every count.
+    fn anonymize(&self, rng: &mut Rng, text: &str) -> crate::Result<String> {
+        let mut groups: Vec<Vec<String>> = Vec::new();
+        for group in super::parse(text, day6::parse)? {
+            let mut questions: Vec<char> = ('a'..='z').collect();
+            rng.shuffle(&mut questions);
+            let relabel = |question: &char| match *question {
+                'a'..='z' => questions[(*question as u8 - b'a') as usize],
+                other => other,
+            };
+
+            let mut passengers: Vec<String> = Vec::new();
+            for answers in group.answers() {
+                let mut answers: Vec<char> = answers.iter().map(relabel).collect();
+                rng.shuffle(&mut answers);
+                passengers.push(answers.into_iter().collect());
+            }
+            rng.shuffle(&mut passengers);
+            groups.push(passengers);
+        }
+        rng.shuffle(&mut groups);
+
+        let groups: Vec<String> = groups.iter().map(|group| super::lines(group)).collect();
+        return Ok(groups.join("\n"));
+    }
+
+    /// Any letters make a declaration.
+    fn can_invalidate(&self) -> bool {
+        false
+    }
+}
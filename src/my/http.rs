@@ -0,0 +1,402 @@
+//! Just enough HTTP/1.1 to talk to the puzzle site.
+//!
+//! Plain `http://` URLs are served over a [TcpStream]. There is no TLS in the standard library,
+//! so `https://` URLs are handed to the `curl` command instead.
+
+use std::io::{Read, Write};
+use std::net::TcpStream;
+use std::process::{Command, Stdio};
+use std::time::Duration;
+
+/// How long to wait on the network before giving up.
+const TIMEOUT: Duration = Duration::from_secs(30);
+
+const USER_AGENT: &str = concat!("aoc_2020/", env!("CARGO_PKG_VERSION"));
+
+/// A parsed `http://` or `https://` URL.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Url {
+    pub https: bool,
+    pub host: String,
+    pub port: u16,
+    /// Starts with `/`.
+    pub path: String,
+}
+
+impl Url {
+    pub fn parse(url: &str) -> crate::Result<Self> {
+        let (https, rest) = if let Some(rest) = url.strip_prefix("http://") {
+            (false, rest)
+        } else if let Some(rest) = url.strip_prefix("https://") {
+            (true, rest)
+        } else {
+            return Err(crate::Error::parse(format!(
+                "{} is not an http(s) URL",
+                url
+            )));
+        };
+
+        let (authority, path) = match rest.find('/') {
+            Some(slash) => rest.split_at(slash),
+            None => (rest, "/"),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => match port.parse() {
+                Ok(port) => (host, port),
+                Err(e) => {
+                    return Err(
+                        crate::Error::parse(format!("invalid port in {}", url)).with_source(e)
+                    )
+                }
+            },
+            None if https => (authority, 443),
+            None => (authority, 80),
+        };
+        if host.is_empty() {
+            return Err(crate::Error::parse(format!("{} has no host", url)));
+        }
+
+        return Ok(Url {
+            https,
+            host: String::from(host),
+            port,
+            path: String::from(path),
+        });
+    }
+
+    /// This URL with `path` appended, e.g. `/2020/day/1/input`.
+    pub fn join(&self, path: &str) -> Url {
+        let mut url = self.clone();
+        url.path = format!(
+            "{}/{}",
+            self.path.trim_end_matches('/'),
+            path.trim_start_matches('/')
+        );
+        return url;
+    }
+}
+
+impl std::fmt::Display for Url {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let scheme = if self.https { "https" } else { "http" };
+        write!(f, "{}://{}:{}{}", scheme, self.host, self.port, self.path)
+    }
+}
+
+/// A request, build it with [Request::get] or [Request::post_form].
+#[derive(Clone, Debug)]
+pub struct Request {
+    pub method: &'static str,
+    pub url: Url,
+    pub headers: Vec<(String, String)>,
+    pub body: Option<String>,
+}
+
+impl Request {
+    pub fn get(url: Url) -> Self {
+        Self {
+            method: "GET",
+            url,
+            headers: Vec::new(),
+            body: None,
+        }
+    }
+
+    /// A POST of an `application/x-www-form-urlencoded` form.
+    pub fn post_form(url: Url, fields: &[(&str, &str)]) -> Self {
+        let body: Vec<String> = fields
+            .iter()
+            .map(|(key, value)| format!("{}={}", url_encode(key), url_encode(value)))
+            .collect();
+        return Self {
+            method: "POST",
+            url,
+            headers: Vec::new(),
+            body: Some(body.join("&")),
+        }
+        .with_header("Content-Type", "application/x-www-form-urlencoded");
+    }
+
+    pub fn with_header(mut self, name: &str, value: &str) -> Self {
+        self.headers.push((String::from(name), String::from(value)));
+        self
+    }
+
+    /// Sends the request and waits for the whole response.
+    pub fn send(&self) -> crate::Result<Response> {
+        let failed = |e: std::io::Error| {
+            crate::Error::io(format!("{} {} failed", self.method, self.url)).with_source(e)
+        };
+        if self.url.https {
+            return self.send_with_curl().map_err(failed)?;
+        }
+
+        let mut stream =
+            TcpStream::connect((self.url.host.as_str(), self.url.port)).map_err(failed)?;
+        stream.set_read_timeout(Some(TIMEOUT)).map_err(failed)?;
+        stream.set_write_timeout(Some(TIMEOUT)).map_err(failed)?;
+
+        let mut head = format!(
+            "{} {} HTTP/1.1\r\nHost: {}\r\nUser-Agent: {}\r\nConnection: close\r\n",
+            self.method, self.url.path, self.url.host, USER_AGENT
+        );
+        for (name, value) in self.headers.iter() {
+            head.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        let body = self.body.as_deref().unwrap_or("");
+        if self.body.is_some() {
+            head.push_str(&format!("Content-Length: {}\r\n", body.len()));
+        }
+        head.push_str("\r\n");
+
+        stream.write_all(head.as_bytes()).map_err(failed)?;
+        stream.write_all(body.as_bytes()).map_err(failed)?;
+        let mut raw = Vec::new();
+        stream.read_to_end(&mut raw).map_err(failed)?;
+        return Response::parse(&raw, true);
+    }
+
+    /// Sends the request with `curl`, which prints the same raw response with `-i`.
+    ///
+    /// Headers are passed on stdin so the session cookie never shows up in the process list.
+    fn send_with_curl(&self) -> std::io::Result<crate::Result<Response>> {
+        let mut command = Command::new("curl");
+        command
+            .args(["--silent", "--show-error", "--include", "--header", "@-"])
+            .args(["--max-time", &TIMEOUT.as_secs().to_string()])
+            .args(["--user-agent", USER_AGENT, "--request", self.method]);
+        if let Some(body) = &self.body {
+            command.args(["--data-binary", body]);
+        }
+        command.arg(self.url.to_string());
+
+        let mut child = command
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()?;
+        if let Some(mut stdin) = child.stdin.take() {
+            for (name, value) in self.headers.iter() {
+                writeln!(stdin, "{}: {}", name, value)?;
+            }
+        }
+        let output = child.wait_with_output()?;
+        if !output.status.success() {
+            let stderr = String::from_utf8_lossy(&output.stderr);
+            return Err(std::io::Error::other(format!("curl: {}", stderr.trim())));
+        }
+        // curl has already undone any chunked transfer encoding
+        return Ok(Response::parse(&output.stdout, false));
+    }
+}
+
+/// A response, with its body decoded as (lossy) UTF-8.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Response {
+    pub status: u16,
+    pub headers: Vec<(String, String)>,
+    pub body: String,
+}
+
+impl Response {
+    /// Parses a raw HTTP/1.x response, `dechunk` undoes a chunked transfer encoding.
+    pub fn parse(raw: &[u8], dechunk: bool) -> crate::Result<Self> {
+        let malformed =
+            |what: &str| crate::Error::parse(format!("malformed HTTP response: {}", what));
+
+        let split = match raw.windows(4).position(|w| w == b"\r\n\r\n") {
+            Some(split) => split,
+            None => return Err(malformed("no end of headers")),
+        };
+        let head = String::from_utf8_lossy(&raw[..split]);
+        let mut body = &raw[split + 4..];
+
+        let mut lines = head.split("\r\n");
+        let status_line = lines.next().unwrap_or("");
+        let status = match status_line
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .as_slice()
+        {
+            [version, status, ..] if version.starts_with("HTTP/") => match status.parse() {
+                Ok(status) => status,
+                Err(_) => return Err(malformed(status_line)),
+            },
+            _ => return Err(malformed(status_line)),
+        };
+
+        let mut headers = Vec::new();
+        for line in lines {
+            match line.split_once(':') {
+                Some((name, value)) => {
+                    headers.push((String::from(name.trim()), String::from(value.trim())))
+                }
+                None => return Err(malformed(line)),
+            }
+        }
+
+        let mut response = Response {
+            status,
+            headers,
+            body: String::new(),
+        };
+        let chunked = response
+            .header("Transfer-Encoding")
+            .is_some_and(|encoding| encoding.eq_ignore_ascii_case("chunked"));
+        let decoded;
+        if dechunk && chunked {
+            decoded = decode_chunked(body).ok_or_else(|| malformed("bad chunked body"))?;
+            body = &decoded;
+        } else if let Some(length) = response.header("Content-Length") {
+            let length: usize = length
+                .parse()
+                .map_err(|_| malformed("bad Content-Length"))?;
+            body = &body[..length.min(body.len())];
+        }
+        response.body = String::from_utf8_lossy(body).into_owned();
+        return Ok(response);
+    }
+
+    /// The value of the first header called `name`, ignoring case.
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(header, _)| header.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+}
+
+fn decode_chunked(mut body: &[u8]) -> Option<Vec<u8>> {
+    let mut decoded = Vec::new();
+    loop {
+        let end = body.windows(2).position(|w| w == b"\r\n")?;
+        let size = std::str::from_utf8(&body[..end]).ok()?;
+        // Chunk extensions follow a ';'
+        let size = size.split(';').next()?.trim();
+        let size = usize::from_str_radix(size, 16).ok()?;
+        body = &body[end + 2..];
+        if size == 0 {
+            return Some(decoded);
+        }
+        decoded.extend_from_slice(body.get(..size)?);
+        body = body.get(size + 2..)?;
+    }
+}
+
+/// Percent-encodes everything but unreserved characters.
+pub fn url_encode(s: &str) -> String {
+    let mut encoded = String::new();
+    for byte in s.bytes() {
+        match byte {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'_' | b'.' | b'~' => {
+                encoded.push(byte as char)
+            }
+            _ => encoded.push_str(&format!("%{:02X}", byte)),
+        }
+    }
+    return encoded;
+}
+
+/// A stand-in server for tests.
+#[cfg(test)]
+pub(crate) mod mock {
+    use std::io::{BufRead, BufReader, Read, Write};
+    use std::net::TcpListener;
+    use std::thread;
+
+    /// Serves `responses` in order, one per connection, on a local port.
+    ///
+    /// Returns the server's base URL and a handle that yields the raw requests it received.
+    pub fn serve(responses: Vec<String>) -> (String, thread::JoinHandle<Vec<String>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let handle = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for response in responses {
+                let (stream, _) = listener.accept().unwrap();
+                let mut reader = BufReader::new(stream);
+
+                let mut request = String::new();
+                let mut length = 0;
+                loop {
+                    let mut line = String::new();
+                    reader.read_line(&mut line).unwrap();
+                    if let Some(value) = line.strip_prefix("Content-Length: ") {
+                        length = value.trim().parse().unwrap();
+                    }
+                    request.push_str(&line);
+                    if line == "\r\n" {
+                        break;
+                    }
+                }
+                let mut body = vec![0; length];
+                reader.read_exact(&mut body).unwrap();
+                request.push_str(&String::from_utf8(body).unwrap());
+                requests.push(request);
+
+                reader.get_mut().write_all(response.as_bytes()).unwrap();
+            }
+            requests
+        });
+        return (url, handle);
+    }
+
+    /// A complete response with `status` and `body`.
+    pub fn response(status: u16, body: &str) -> String {
+        format!(
+            "HTTP/1.1 {} X\r\nContent-Length: {}\r\n\r\n{}",
+            status,
+            body.len(),
+            body
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parses_urls() {
+        let url = Url::parse("http://localhost:8080/aoc").unwrap();
+        assert_eq!(url.host, "localhost");
+        assert_eq!(url.port, 8080);
+        assert_eq!(url.join("2020/day/1/input").path, "/aoc/2020/day/1/input");
+
+        let url = Url::parse("https://adventofcode.com").unwrap();
+        assert_eq!((url.https, url.port, url.path.as_str()), (true, 443, "/"));
+        assert_eq!(url.join("/2020").path, "/2020");
+
+        assert!(Url::parse("ftp://example.com").is_err());
+        assert!(Url::parse("http://:80/").is_err());
+    }
+
+    #[test]
+    fn test_decodes_chunked_bodies() {
+        let raw = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n4\r\n1721\r\n5;x=y\r\n\n979\n\r\n0\r\n\r\n";
+        let response = Response::parse(raw, true).unwrap();
+        assert_eq!(response.status, 200);
+        assert_eq!(response.body, "1721\n979\n");
+    }
+
+    #[test]
+    fn test_round_trips_through_a_server() {
+        let (url, server) = mock::serve(vec![mock::response(200, "ok")]);
+        let url = Url::parse(&url).unwrap().join("submit");
+        let response = Request::post_form(url, &[("answer", "a b&c")])
+            .with_header("Cookie", "session=abc")
+            .send()
+            .unwrap();
+        assert!(response.is_success());
+        assert_eq!(response.body, "ok");
+
+        let request = &server.join().unwrap()[0];
+        assert!(request.starts_with("POST /submit HTTP/1.1\r\n"));
+        assert!(request.contains("Cookie: session=abc\r\n"));
+        assert!(request.ends_with("\r\n\r\nanswer=a%20b%26c"));
+    }
+}
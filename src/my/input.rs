@@ -1,51 +1,505 @@
+use std::env;
+use std::fs;
 use std::fs::File;
 use std::io;
+use std::marker::PhantomData;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 pub use std::io::BufRead;
 
-type BufReader = io::BufReader<File>;
+type BufReader = Box<dyn BufRead>;
+
+/// Environment variable that overrides the directory puzzle inputs are read from.
+pub const INPUTS_ENV: &str = "AOC_INPUTS";
+
+/// Environment variable selecting whose inputs to use, see [Inputs::with_profile].
+pub const PROFILE_ENV: &str = "AOC_PROFILE";
+
+/// Environment variable selecting the event year of the inputs, see [Inputs::with_year].
+pub const YEAR_ENV: &str = "AOC_YEAR";
+
+const DEFAULT_INPUTS_ROOT: &str = "inputs";
+
+/// Directory beneath an inputs root holding the worked examples from the puzzle statements.
+pub const EXAMPLES_DIR: &str = "examples";
+
+/// Locates each day's puzzle input beneath an inputs root directory.
+///
+/// Inputs are kept per profile, i.e. per person, and per event year as
+/// `<root>/<profile>/<year>/day_N`. The [default profile](crate::answers::DEFAULT_PROFILE)'s
+/// inputs for [crate::config::YEAR] may instead sit directly in the root as `<root>/day_N`.
+#[derive(Clone, Debug)]
+pub struct Inputs {
+    root: PathBuf,
+    profile: String,
+    year: u16,
+}
+
+impl Inputs {
+    pub fn new<P: Into<PathBuf>>(root: P) -> Self {
+        Self {
+            root: root.into(),
+            profile: String::from(crate::answers::DEFAULT_PROFILE),
+            year: crate::config::YEAR,
+        }
+    }
+
+    /// Uses the root named by [INPUTS_ENV], falling back to `inputs` in the current directory,
+    /// and the profile and year named by [PROFILE_ENV] and [YEAR_ENV] if they are set.
+    pub fn from_env() -> crate::Result<Self> {
+        let mut inputs = match env::var_os(INPUTS_ENV) {
+            Some(root) => Self::new(root),
+            None => Self::new(DEFAULT_INPUTS_ROOT),
+        };
+        if let Ok(profile) = env::var(PROFILE_ENV) {
+            inputs = inputs.with_profile(&profile)?;
+        }
+        if let Ok(year) = env::var(YEAR_ENV) {
+            let year = year.parse().map_err(|e| {
+                crate::Error::parse(format!("{} is not a year", YEAR_ENV)).with_source(e)
+            })?;
+            inputs = inputs.with_year(year);
+        }
+        return Ok(inputs);
+    }
+
+    /// Selects whose inputs to use, `profile` names a directory beneath the root.
+    pub fn with_profile(mut self, profile: &str) -> crate::Result<Self> {
+        let valid = !profile.is_empty()
+            && profile != EXAMPLES_DIR
+            && profile
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+        if !valid {
+            return Err(crate::Error::validation(format!(
+                "'{}' is not a valid profile name",
+                profile
+            )));
+        }
+        self.profile = String::from(profile);
+        return Ok(self);
+    }
+
+    pub fn with_year(mut self, year: u16) -> Self {
+        self.year = year;
+        self
+    }
+
+    pub fn root(&self) -> &Path {
+        &self.root
+    }
+
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    pub fn year(&self) -> u16 {
+        self.year
+    }
+
+    /// The directory holding the selected profile's inputs for the selected year.
+    ///
+    /// That is the root itself for the default profile and year, unless the root has a
+    /// directory for them.
+    pub fn dir(&self) -> PathBuf {
+        let dir = self
+            .root
+            .join(&self.profile)
+            .join(self.year.to_string());
+        let is_default = self.profile == crate::answers::DEFAULT_PROFILE
+            && self.year == crate::config::YEAR;
+        if is_default && !dir.is_dir() {
+            return self.root.clone();
+        }
+        return dir;
+    }
+
+    /// The path `day`'s input is expected at.
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir().join(format!("day_{}", day))
+    }
+
+    /// Every profile with inputs for the selected year, in name order.
+    ///
+    /// The default profile is included if it has inputs in the root.
+    pub fn profiles(&self) -> crate::Result<Vec<String>> {
+        let entries = fs::read_dir(&self.root).map_err(|e| {
+            crate::Error::io(format!("could not list {}", self.root.display())).with_source(e)
+        })?;
+
+        let mut profiles = Vec::new();
+        for entry in entries {
+            let entry = entry.map_err(|e| {
+                crate::Error::io(format!("could not list {}", self.root.display())).with_source(e)
+            })?;
+            let name = entry.file_name().to_string_lossy().into_owned();
+            if name != EXAMPLES_DIR && entry.path().join(self.year.to_string()).is_dir() {
+                profiles.push(name);
+            }
+        }
+
+        let default = String::from(crate::answers::DEFAULT_PROFILE);
+        let legacy = Self::new(self.root.clone()).with_year(self.year);
+        if !profiles.contains(&default) && legacy.dir() == self.root && legacy.has_any_day() {
+            profiles.push(default);
+        }
+        profiles.sort();
+        return Ok(profiles);
+    }
+
+    fn has_any_day(&self) -> bool {
+        (1..=25).any(|day| self.path(day).is_file())
+    }
+
+    /// The path of a worked example for `day`, `index` picks one of several examples.
+    ///
+    /// Examples live in [EXAMPLES_DIR] as `day_N`, further examples for the same day as
+    /// `day_N_2`, `day_N_3` and so on.
+    pub fn example_path(&self, day: u8, index: Option<usize>) -> PathBuf {
+        let name = match index {
+            Some(index) if index > 1 => format!("day_{}_{}", day, index),
+            _ => format!("day_{}", day),
+        };
+        return self.root.join(EXAMPLES_DIR).join(name);
+    }
+
+    /// The path of the [crate::answers::AnswerKey], shared by every profile and year.
+    pub fn answers_path(&self) -> PathBuf {
+        self.root.join(crate::answers::ANSWERS_FILE)
+    }
+
+    /// The path of the selected profile's [crate::submit::SubmissionLog] for the selected year.
+    pub fn submissions_path(&self) -> PathBuf {
+        self.dir().join(crate::submit::SUBMISSIONS_FILE)
+    }
+
+    pub fn reader(&self, day: u8) -> crate::Result<InputReader> {
+        InputReader::from_path(self.path(day))
+    }
+}
 
 pub struct InputReader {
     reader: BufReader,
+    source: String,
 }
 
 impl InputReader {
-    pub fn new(day: u8) -> Self {
-        let file = File::open(Self::input_file(day)).expect("could not open input file");
-        let reader = BufReader::new(file);
+    /// Opens `day`'s input from the default [Inputs] location.
+    pub fn new(day: u8) -> crate::Result<Self> {
+        Inputs::from_env()?.reader(day)
+    }
+
+    pub fn from_path<P: AsRef<Path>>(path: P) -> crate::Result<Self> {
+        let path = path.as_ref();
+        let file = File::open(path).map_err(|e| {
+            crate::Error::io(format!("could not open {}", path.display())).with_source(e)
+        })?;
+        return Ok(Self::from_reader(
+            io::BufReader::new(file),
+            path.display().to_string(),
+        ));
+    }
+
+    pub fn stdin() -> Self {
+        Self::from_reader(io::BufReader::new(io::stdin()), String::from("<stdin>"))
+    }
+
+    /// Wraps an arbitrary reader, `source` describes where it came from.
+    pub fn from_reader<R: BufRead + 'static>(reader: R, source: String) -> Self {
         Self {
-            reader,
+            reader: Box::new(reader),
+            source,
         }
     }
 
+    /// Reads from in-memory `text`, `source` describes where it came from.
+    pub fn from_text(text: String, source: String) -> Self {
+        Self::from_reader(io::Cursor::new(text), source)
+    }
+
+    /// Consumes the whole input as one string.
+    pub fn read_to_string(mut self) -> crate::Result<String> {
+        let mut text = String::new();
+        self.reader.read_to_string(&mut text).map_err(|e| {
+            crate::Error::io(format!("failed to read {}", self.source)).with_source(e)
+        })?;
+        return Ok(text);
+    }
+
+    /// Human readable description of where the input is read from, usually a path.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
     pub fn lines(self) -> Lines {
         Lines {
-            inner: self.reader.lines()
+            inner: self.reader.lines(),
+            source: self.source,
+            line_no: 0,
         }
     }
 
-    fn input_file(day: u8) -> String {
-        format!("inputs/day_{}", day)
+    /// Iterates the blank line separated records of the input, see [Lines::records].
+    pub fn records(self) -> Records {
+        self.lines().records()
+    }
+
+    /// Parses every line as a `T`, see [Lines::parse].
+    pub fn parse_lines<T>(self) -> ParseLines<T>
+    where
+        T: FromStr,
+        T::Err: Into<crate::Error>,
+    {
+        self.lines().parse()
     }
 }
 
 pub struct Lines {
     inner: io::Lines<BufReader>,
+    source: String,
+    // Number of lines consumed so far.
+    line_no: usize,
+}
+
+impl Lines {
+    /// Where the lines are read from, see [InputReader::source].
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+
+    /// The 1-based line number of the most recently yielded line.
+    pub fn line_number(&self) -> usize {
+        self.line_no
+    }
+
+    /// Groups lines into records, one record per block of lines terminated by a blank line or the
+    /// end of input.
+    ///
+    /// Runs of blank lines and leading or trailing blank lines never produce empty records.
+    pub fn records(self) -> Records {
+        Records {
+            lines: self,
+            record_line_no: 0,
+        }
+    }
+
+    /// Parses each line with `T`'s [FromStr] implementation.
+    ///
+    /// Trailing whitespace is ignored. Failures carry the 1-based line number and the offending
+    /// line.
+    pub fn parse<T>(self) -> ParseLines<T>
+    where
+        T: FromStr,
+        T::Err: Into<crate::Error>,
+    {
+        ParseLines {
+            lines: self,
+            parsed: PhantomData,
+        }
+    }
 }
 
 impl Iterator for Lines {
-    type Item = String;
+    type Item = crate::Result<String>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let result = self.inner.next()?;
+        self.line_no += 1;
+
+        let line = result.map_err(|e| {
+            crate::Error::io(format!("failed to read line {}", self.line_no))
+                .with_source(e)
+                .with_file(self.source.as_str())
+        });
+        return Some(line);
+    }
+}
+
+pub struct Records {
+    lines: Lines,
+    // Line number the most recently yielded record started on.
+    record_line_no: usize,
+}
+
+impl Records {
+    /// The 1-based line number of the first line of the most recently yielded record.
+    pub fn line_number(&self) -> usize {
+        self.record_line_no
+    }
+
+    /// Where the records are read from, see [InputReader::source].
+    pub fn source(&self) -> &str {
+        self.lines.source()
+    }
+}
+
+impl Iterator for Records {
+    type Item = crate::Result<Vec<String>>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        // Unwrap the inner result and just panic
-        if let Some(result) = self.inner.next() {
-            if let Ok(result) = result {
-                return Some(result);
-            } else {
-                panic!("failed to read line");
+        let mut record = Vec::new();
+        while let Some(line) = self.lines.next() {
+            let line = match line {
+                Ok(line) => line,
+                Err(e) => return Some(Err(e)),
+            };
+
+            // Whitespace-only lines count as blank, this also covers a stray '\r'
+            if line.trim().is_empty() {
+                if record.is_empty() {
+                    continue;
+                }
+                return Some(Ok(record));
             }
-        } else {
+
+            if record.is_empty() {
+                self.record_line_no = self.lines.line_number();
+            }
+            record.push(line);
+        }
+
+        // The final record has no trailing blank line
+        if record.is_empty() {
             return None;
+        } else {
+            return Some(Ok(record));
+        }
+    }
+}
+
+pub struct ParseLines<T> {
+    lines: Lines,
+    parsed: PhantomData<fn() -> T>,
+}
+
+impl<T> Iterator for ParseLines<T>
+where
+    T: FromStr,
+    T::Err: Into<crate::Error>,
+{
+    type Item = crate::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = match self.lines.next()? {
+            Ok(line) => line,
+            Err(e) => return Some(Err(e)),
+        };
+
+        let parsed = line.trim_end().parse::<T>().map_err(|e| {
+            e.into()
+                .with_line(self.lines.line_number(), &line)
+                .with_file(self.lines.source())
+        });
+        return Some(parsed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_inputs_path_is_under_root() {
+        let inputs = Inputs::new("/some/where");
+        assert_eq!(inputs.path(4), PathBuf::from("/some/where/day_4"));
+    }
+
+    #[test]
+    fn test_profiles_have_their_own_directories() {
+        let root = env::temp_dir().join(format!("aoc-profiles-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("alice/2020")).unwrap();
+        fs::create_dir_all(root.join("bob/2021")).unwrap();
+        fs::create_dir_all(root.join(EXAMPLES_DIR)).unwrap();
+        fs::write(root.join("day_1"), "1721\n").unwrap();
+
+        let inputs = Inputs::new(&root);
+        assert_eq!(inputs.path(1), root.join("day_1"));
+        let alice = inputs.clone().with_profile("alice").unwrap();
+        assert_eq!(alice.path(1), root.join("alice/2020/day_1"));
+        assert_eq!(inputs.clone().with_year(2021).path(1), root.join("default/2021/day_1"));
+        assert_eq!(inputs.profiles().unwrap(), vec!["alice", "default"]);
+        assert_eq!(inputs.clone().with_year(2021).profiles().unwrap(), vec!["bob"]);
+
+        // Once the default profile has a directory the flat layout is no longer used
+        fs::create_dir_all(root.join("default/2020")).unwrap();
+        assert_eq!(inputs.path(1), root.join("default/2020/day_1"));
+
+        assert!(inputs.clone().with_profile("../etc").is_err());
+        assert!(inputs.clone().with_profile(EXAMPLES_DIR).is_err());
+        fs::remove_dir_all(root).unwrap();
+    }
+
+    #[test]
+    fn test_missing_input_is_an_error() {
+        assert!(InputReader::from_path("/definitely/not/an/input").is_err());
+    }
+
+    fn reader(s: &'static str) -> InputReader {
+        InputReader::from_text(String::from(s), String::from("test"))
+    }
+
+    mod parse_lines {
+        use super::*;
+
+        #[test]
+        fn test_parses_every_line() {
+            let values: crate::Result<Vec<i32>> = reader("1\n-2\n3").parse_lines().collect();
+            assert_eq!(values.unwrap(), vec![1, -2, 3]);
+        }
+
+        #[test]
+        fn test_reports_failing_line() {
+            let mut values = reader("1\n2\nthree\n4").parse_lines::<i32>();
+            assert!(values.next().unwrap().is_ok());
+            assert!(values.next().unwrap().is_ok());
+
+            let e = values.next().unwrap().unwrap_err();
+            assert_eq!(e.line_number(), Some(3));
+            assert_eq!(e.kind(), crate::ErrorKind::Parse);
+            assert_eq!(e.location().unwrap().file.as_deref(), Some("test"));
+            assert!(e.to_string().contains("three"));
+
+            // Parsing continues past the failure
+            assert_eq!(values.next().unwrap().unwrap(), 4);
+        }
+    }
+
+    mod records {
+        use super::*;
+
+        #[test]
+        fn test_includes_final_record() {
+            let records: crate::Result<Vec<_>> = reader("a\nb\n\nc").records().collect();
+            assert_eq!(records.unwrap(), vec![vec!["a", "b"], vec!["c"]]);
+        }
+
+        #[test]
+        fn test_skips_repeated_and_trailing_blank_lines() {
+            let records: crate::Result<Vec<_>> = reader("\na\n\n\n\nb\n\n\n").records().collect();
+            assert_eq!(records.unwrap(), vec![vec!["a"], vec!["b"]]);
+        }
+
+        #[test]
+        fn test_handles_crlf() {
+            let records: crate::Result<Vec<_>> = reader("a\r\nb\r\n\r\nc\r\n").records().collect();
+            assert_eq!(records.unwrap(), vec![vec!["a", "b"], vec!["c"]]);
+        }
+
+        #[test]
+        fn test_tracks_record_line_numbers() {
+            let mut records = reader("a\n\n\nb\nc\n").records();
+            records.next();
+            assert_eq!(records.line_number(), 1);
+            records.next();
+            assert_eq!(records.line_number(), 4);
+        }
+
+        #[test]
+        fn test_empty_input_has_no_records() {
+            assert_eq!(reader("").records().count(), 0);
+            assert_eq!(reader("\n\n").records().count(), 0);
         }
     }
 }
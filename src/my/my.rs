@@ -1,4 +1,19 @@
+pub mod answers;
+pub mod bench;
+pub mod config;
+pub mod days;
+pub mod differential;
+pub mod fetch;
+pub mod generate;
+pub mod http;
 pub mod input;
+pub mod json;
+pub mod pool;
+pub mod runner;
+pub mod scaffold;
+pub mod solution;
+pub mod submit;
+pub mod testing;
 
 mod error;
-pub use error::{Error, Result};
+pub use error::{Error, ErrorKind, Location, Result};
@@ -0,0 +1,96 @@
+//! A small fixed-size thread pool for running independent jobs, see [map].
+
+use std::any::Any;
+use std::panic::{self, AssertUnwindSafe};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::mpsc;
+use std::thread;
+
+/// Calls `f` on every item using up to `threads` threads, returning the results in item order.
+///
+/// A panic in `f` only fails its own item, as `Err` with the panic message, the other items
+/// still run.
+pub fn map<T, R, F>(items: &[T], threads: usize, f: F) -> Vec<Result<R, String>>
+where
+    T: Sync,
+    R: Send,
+    F: Fn(&T) -> R + Sync,
+{
+    let threads = threads.clamp(1, items.len().max(1));
+    let next = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..threads {
+            let sender = sender.clone();
+            let (next, f) = (&next, &f);
+            scope.spawn(move || loop {
+                let index = next.fetch_add(1, Ordering::Relaxed);
+                let item = match items.get(index) {
+                    Some(item) => item,
+                    None => return,
+                };
+                let result = panic::catch_unwind(AssertUnwindSafe(|| f(item)));
+                // The receiver outlives the scope, so sending can not fail
+                let _ = sender.send((index, result.map_err(panic_message)));
+            });
+        }
+    });
+    drop(sender);
+
+    let mut results: Vec<Option<Result<R, String>>> = (0..items.len()).map(|_| None).collect();
+    for (index, result) in receiver {
+        results[index] = Some(result);
+    }
+    return results
+        .into_iter()
+        .map(|result| result.expect("every item is run exactly once"))
+        .collect();
+}
+
+/// The number of threads to use by default, one per available core.
+pub fn default_threads() -> usize {
+    thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// The message a panic was started with, if it was a string.
+fn panic_message(payload: Box<dyn Any + Send>) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        return String::from(*message);
+    }
+    if let Some(message) = payload.downcast_ref::<String>() {
+        return message.clone();
+    }
+    return String::from("panicked without a message");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keeps_item_order() {
+        let items: Vec<u64> = (0..50).collect();
+        let results = map(&items, 4, |n| n * n);
+        let squares: Vec<u64> = results.into_iter().map(Result::unwrap).collect();
+        assert_eq!(squares, items.iter().map(|n| n * n).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn test_isolates_panics() {
+        let results = map(&[1, 2, 3], 2, |&n| {
+            if n == 2 {
+                panic!("day {} exploded", n);
+            }
+            n
+        });
+        assert_eq!(results[0], Ok(1));
+        assert_eq!(results[1], Err(String::from("day 2 exploded")));
+        assert_eq!(results[2], Ok(3));
+    }
+
+    #[test]
+    fn test_handles_no_items() {
+        assert!(map(&[] as &[u8], 8, |&n| n).is_empty());
+    }
+}
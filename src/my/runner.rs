@@ -0,0 +1,364 @@
+use crate::bench;
+use crate::bench::nanos;
+use crate::days;
+use crate::fetch;
+use crate::input::{InputReader, Inputs};
+use crate::json;
+use crate::pool;
+use crate::solution::{Answer, Part, Puzzle};
+use std::env;
+use std::io::Write;
+use std::process;
+use std::time::{Duration, Instant};
+
+/// Parses `puzzle`'s input from `reader` and prints the answer to each of `parts`.
+pub fn run(puzzle: &dyn Puzzle, reader: InputReader, parts: &[Part]) -> crate::Result<()> {
+    let report = solve(puzzle, Ok(reader), parts);
+    if let Some(e) = report.error {
+        return Err(e);
+    }
+    for solved in report.parts {
+        println!("Day {} part {}: {}", report.day, solved.part, solved.answer?);
+    }
+    return Ok(());
+}
+
+/// The outcome of solving one day, see [solve].
+#[derive(Debug)]
+pub struct Report {
+    pub day: u8,
+    /// Where the input was read from, None if it could not be opened.
+    pub input: Option<String>,
+    pub parse_time: Option<Duration>,
+    /// Why the input could not be read or parsed, no part is solved if set.
+    pub error: Option<crate::Error>,
+    pub parts: Vec<PartReport>,
+}
+
+/// The outcome of solving one part.
+#[derive(Debug)]
+pub struct PartReport {
+    pub part: Part,
+    pub answer: crate::Result<Answer>,
+    pub time: Duration,
+}
+
+/// Parses `puzzle`'s input from `reader` and solves each of `parts`, timing every step.
+///
+/// Solving carries on with the other parts when one fails.
+pub fn solve(puzzle: &dyn Puzzle, reader: crate::Result<InputReader>, parts: &[Part]) -> Report {
+    let mut report = Report {
+        day: puzzle.day(),
+        input: None,
+        parse_time: None,
+        error: None,
+        parts: Vec::new(),
+    };
+
+    let reader = match reader {
+        Ok(reader) => reader,
+        Err(e) => {
+            report.error = Some(e);
+            return report;
+        }
+    };
+    report.input = Some(String::from(reader.source()));
+
+    let start = Instant::now();
+    let input = match puzzle.parse(reader) {
+        Ok(input) => input,
+        Err(e) => {
+            report.error = Some(e);
+            return report;
+        }
+    };
+    report.parse_time = Some(start.elapsed());
+
+    for &part in parts {
+        let start = Instant::now();
+        let answer = puzzle.solve(input.as_ref(), part);
+        report.parts.push(PartReport {
+            part,
+            answer,
+            time: start.elapsed(),
+        });
+    }
+    return report;
+}
+
+impl Report {
+    /// The first failure, of reading and parsing the input or of solving a part.
+    pub fn first_error(&self) -> Option<&crate::Error> {
+        if let Some(e) = &self.error {
+            return Some(e);
+        }
+        return self.parts.iter().find_map(|solved| solved.answer.as_ref().err());
+    }
+
+    /// One JSON object per part of `parts`, parts that were never solved carry the input error.
+    ///
+    /// Objects have `day`, `part`, `answer`, `type`, `parse_ns`, `solve_ns`, `input` and `error`
+    /// members, `null` where there is nothing to report.
+    pub fn to_json(&self, parts: &[Part]) -> Vec<json::Value> {
+        let mut objects = Vec::new();
+        for &part in parts {
+            let solved = self.parts.iter().find(|solved| solved.part == part);
+            let (answer, error) = match solved.map(|solved| &solved.answer) {
+                Some(Ok(answer)) => (Some(answer), None),
+                Some(Err(e)) => (None, Some(e)),
+                None => (None, self.error.as_ref()),
+            };
+
+            objects.push(
+                json::Value::object()
+                    .with("day", self.day)
+                    .with("part", part.number())
+                    .with("answer", answer.map(json::Value::from))
+                    .with("type", answer.and_then(Answer::type_name))
+                    .with("parse_ns", self.parse_time.map(nanos))
+                    .with("solve_ns", solved.map(|solved| nanos(solved.time)))
+                    .with("input", self.input.clone())
+                    .with("error", error.map(error_json)),
+            );
+        }
+        return objects;
+    }
+}
+
+fn error_json(e: &crate::Error) -> json::Value {
+    let location = e.location();
+    return json::Value::object()
+        .with("kind", e.kind().name())
+        .with("message", e.to_string())
+        .with("file", location.and_then(|l| l.file.clone()))
+        .with("line", location.map(|l| l.line))
+        .with("column", location.and_then(|l| l.column));
+}
+
+/// How solving one day went in [solve_all].
+#[derive(Debug)]
+pub struct DayResult {
+    pub day: u8,
+    /// The day's report, or the message of the panic that cut it short.
+    pub report: Result<Report, String>,
+    /// Wall time spent on the day, None if it panicked.
+    pub elapsed: Option<Duration>,
+}
+
+impl DayResult {
+    /// `ok`, `failed` or `panicked`.
+    pub fn status(&self) -> &'static str {
+        match &self.report {
+            Ok(report) if report.first_error().is_none() => "ok",
+            Ok(_) => "failed",
+            Err(_) => "panicked",
+        }
+    }
+
+    /// Like [Report::to_json], a panic is reported as an error of kind `panic`.
+    pub fn to_json(&self, parts: &[Part]) -> Vec<json::Value> {
+        let message = match &self.report {
+            Ok(report) => return report.to_json(parts),
+            Err(message) => message,
+        };
+
+        let mut objects = Vec::new();
+        for &part in parts {
+            let error = json::Value::object()
+                .with("kind", "panic")
+                .with("message", message.as_str());
+            objects.push(
+                json::Value::object()
+                    .with("day", self.day)
+                    .with("part", part.number())
+                    .with("answer", json::Value::Null)
+                    .with("type", json::Value::Null)
+                    .with("parse_ns", json::Value::Null)
+                    .with("solve_ns", json::Value::Null)
+                    .with("input", json::Value::Null)
+                    .with("error", error),
+            );
+        }
+        return objects;
+    }
+}
+
+/// Solves `parts` of every puzzle concurrently on up to `threads` threads, reading inputs from
+/// `inputs`.
+///
+/// Results are in the order of `puzzles`. A panicking day is reported as such and does not stop
+/// the others.
+pub fn solve_all(
+    puzzles: &[&'static dyn Puzzle],
+    inputs: &Inputs,
+    parts: &[Part],
+    threads: usize,
+) -> Vec<DayResult> {
+    let results = pool::map(puzzles, threads, |&puzzle| {
+        let start = Instant::now();
+        let report = solve(puzzle, fetch::open(inputs, puzzle.day()), parts);
+        return (report, start.elapsed());
+    });
+
+    return puzzles
+        .iter()
+        .zip(results)
+        .map(|(puzzle, result)| match result {
+            Ok((report, elapsed)) => DayResult {
+                day: puzzle.day(),
+                report: Ok(report),
+                elapsed: Some(elapsed),
+            },
+            Err(message) => DayResult {
+                day: puzzle.day(),
+                report: Err(message),
+                elapsed: None,
+            },
+        })
+        .collect();
+}
+
+/// Writes a table of each day's status and timings, followed by the reason of every failure.
+pub fn write_summary<W: Write>(out: &mut W, results: &[DayResult]) -> crate::Result<()> {
+    writeln!(
+        out,
+        "{:>3}  {:<8} {:>10} {:>10} {:>10} {:>10}",
+        "day", "status", "parse", "part1", "part2", "total"
+    )?;
+    let time = |d: Option<Duration>| d.map_or(String::from("-"), bench::format_duration);
+    for result in results {
+        let report = result.report.as_ref().ok();
+        let part_time = |part: Part| {
+            let solved = report?.parts.iter().find(|solved| solved.part == part)?;
+            return Some(solved.time);
+        };
+        writeln!(
+            out,
+            "{:>3}  {:<8} {:>10} {:>10} {:>10} {:>10}",
+            result.day,
+            result.status(),
+            time(report.and_then(|report| report.parse_time)),
+            time(part_time(Part::One)),
+            time(part_time(Part::Two)),
+            time(result.elapsed),
+        )?;
+    }
+
+    for result in results {
+        match &result.report {
+            Ok(report) => {
+                if let Some(e) = report.first_error() {
+                    writeln!(out, "\nDay {} failed: {}", result.day, e)?;
+                }
+            }
+            Err(message) => writeln!(out, "\nDay {} panicked: {}", result.day, message)?,
+        }
+    }
+    return Ok(());
+}
+
+/// Entry point shared by the per-day binaries, solves both parts of `day`.
+///
+/// Failures are reported on stderr and exit with [crate::ErrorKind::exit_code].
+///
+/// An optional first argument names the input file to use instead of the default location, `-`
+/// reads from stdin.
+pub fn main_for_day(day: u8) {
+    let puzzle = days::get(day).expect("day is not registered");
+    let reader = open_input(day, env::args().nth(1).as_deref());
+
+    if let Err(e) = reader.and_then(|reader| run(puzzle, reader, &Part::ALL)) {
+        eprintln!("Day {} failed: {}", day, e);
+        process::exit(e.kind().exit_code());
+    }
+}
+
+/// Opens `path`, stdin for `-`, or else `day`'s input from the environment's inputs directory.
+pub fn open_input(day: u8, path: Option<&str>) -> crate::Result<InputReader> {
+    match path {
+        Some("-") => Ok(InputReader::stdin()),
+        Some(path) => InputReader::from_path(path),
+        None => Inputs::from_env().and_then(|inputs| fetch::open(&inputs, day)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn reader(s: &str) -> InputReader {
+        InputReader::from_text(String::from(s), String::from("test"))
+    }
+
+    #[test]
+    fn test_json_reports_answers() {
+        let puzzle = days::get(5).unwrap();
+        let report = solve(puzzle, Ok(reader("FFFFFFFLLL\nFFFFFFFLRL\n")), &[Part::One]);
+        let objects = report.to_json(&[Part::One]);
+        let text = objects[0].to_string();
+        assert!(text.starts_with(r#"{"day":5,"part":1,"answer":2,"type":"uint","parse_ns":"#));
+        assert!(text.ends_with(r#","input":"test","error":null}"#));
+    }
+
+    #[test]
+    fn test_json_reports_parse_errors_for_every_part() {
+        let puzzle = days::get(5).unwrap();
+        let report = solve(puzzle, Ok(reader("nope\n")), &Part::ALL);
+        assert_eq!(report.first_error().map(|e| e.kind()), Some(crate::ErrorKind::Parse));
+
+        let objects = report.to_json(&Part::ALL);
+        assert_eq!(objects.len(), 2);
+        for object in objects {
+            let text = object.to_string();
+            assert!(text.contains(r#""answer":null,"type":null"#));
+            assert!(text.contains(r#""error":{"kind":"parse","#));
+            assert!(text.contains(r#""line":1,"#));
+        }
+    }
+
+    struct Explodes;
+
+    impl crate::solution::Solution for Explodes {
+        type Input = ();
+
+        fn day(&self) -> u8 {
+            25
+        }
+
+        fn parse(&self, _reader: InputReader) -> crate::Result<()> {
+            Ok(())
+        }
+
+        fn part_one(&self, _input: &()) -> crate::Result<Answer> {
+            panic!("out of stars");
+        }
+
+        fn part_two(&self, _input: &()) -> crate::Result<Answer> {
+            Ok(Answer::Unsolved)
+        }
+    }
+
+    #[test]
+    fn test_solve_all_isolates_panics() {
+        static EXPLODES: Explodes = Explodes;
+        let root = std::env::temp_dir().join(format!("aoc-runner-{}", std::process::id()));
+        fs::create_dir_all(&root).unwrap();
+        let inputs = Inputs::new(&root);
+        fs::write(inputs.path(6), "abc\n\nab\nac\n").unwrap();
+        fs::write(inputs.path(25), "x\n").unwrap();
+
+        let puzzles = [days::get(6).unwrap(), &EXPLODES as &dyn Puzzle];
+        let results = solve_all(&puzzles, &inputs, &Part::ALL, 2);
+        fs::remove_dir_all(root).unwrap();
+
+        assert_eq!(results[0].status(), "ok");
+        assert_eq!(results[1].status(), "panicked");
+        assert_eq!(results[1].report.as_ref().unwrap_err(), "out of stars");
+
+        let mut summary = Vec::new();
+        write_summary(&mut summary, &results).unwrap();
+        let summary = String::from_utf8(summary).unwrap();
+        assert!(summary.contains("Day 25 panicked: out of stars"));
+    }
+}
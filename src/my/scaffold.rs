@@ -0,0 +1,205 @@
+//! Generates the files for a new day, see [new_day].
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const DAY_TEMPLATE: &str = include_str!("templates/day.rs.in");
+
+const BIN_TEMPLATE: &str = "\
+extern crate my;
+
+fn main() {
+    my::runner::main_for_day({day});
+}
+";
+
+/// Path of the day registry, relative to the repository root.
+pub const REGISTRY_PATH: &str = "src/my/days.rs";
+
+/// Creates everything needed to start on `day` in the repository at `root`.
+///
+/// That is the day's module with unsolved parts and a failing known-answer test, its entry in the
+/// registry, its binary, and empty files for its input and example. Returns the paths it created
+/// or changed, and touches nothing if the day's module already exists.
+pub fn new_day<P: AsRef<Path>>(root: P, day: u8) -> crate::Result<Vec<PathBuf>> {
+    let root = root.as_ref();
+    if !(1..=25).contains(&day) {
+        return Err(crate::Error::validation(format!(
+            "day must be between 1 and 25, got {}",
+            day
+        )));
+    }
+
+    let module_path = root.join(format!("src/my/days/day{}.rs", day));
+    if module_path.exists() {
+        return Err(crate::Error::validation(format!(
+            "{} already exists",
+            module_path.display()
+        )));
+    }
+
+    let registry_path = root.join(REGISTRY_PATH);
+    let registry = register(&read(&registry_path)?, day)?;
+
+    let mut changed = Vec::new();
+    write(&module_path, &render(DAY_TEMPLATE, day))?;
+    changed.push(module_path);
+    write(&registry_path, &registry)?;
+    changed.push(registry_path);
+
+    let bin_path = root.join(format!("src/bin/day_{}.rs", day));
+    let inputs = crate::input::Inputs::new(root.join("inputs"));
+    let created = [
+        (bin_path, render(BIN_TEMPLATE, day)),
+        (inputs.path(day), String::new()),
+        (inputs.example_path(day, None), String::new()),
+    ];
+    for (path, contents) in created {
+        // Never clobber an input that was already downloaded
+        if !path.exists() {
+            write(&path, &contents)?;
+            changed.push(path);
+        }
+    }
+    return Ok(changed);
+}
+
+/// Adds `day` to the source of the day registry, keeping the days in calendar order.
+pub fn register(registry: &str, day: u8) -> crate::Result<String> {
+    let module = format!("pub mod day{};", day);
+    let entry = format!("&day{}::Day{},", day, day);
+    let malformed = |what: &str| crate::Error::parse(format!("{} has no {}", REGISTRY_PATH, what));
+
+    let mut lines: Vec<String> = registry.lines().map(String::from).collect();
+    if lines.iter().any(|line| line.trim() == module) {
+        return Err(crate::Error::validation(format!(
+            "day {} is already registered",
+            day
+        )));
+    }
+
+    // Insert before the first later day, or after the last earlier one
+    let modules: Vec<(usize, u8)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| Some((i, registered_day(line, "pub mod day", ";")?)))
+        .collect();
+    let last = modules.last().ok_or_else(|| malformed("modules"))?.0;
+    let at = modules
+        .iter()
+        .find(|(_, d)| *d > day)
+        .map_or(last + 1, |(i, _)| *i);
+    lines.insert(at, module);
+
+    let entries: Vec<(usize, u8, String)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(i, line)| {
+            let d = registered_day(line, "&day", "::")?;
+            let indent = &line[..line.len() - line.trim_start().len()];
+            Some((i, d, String::from(indent)))
+        })
+        .collect();
+    let (last, _, indent) = entries.last().ok_or_else(|| malformed("DAYS entries"))?;
+    let at = entries
+        .iter()
+        .find(|(_, d, _)| *d > day)
+        .map_or(last + 1, |(i, _, _)| *i);
+    lines.insert(at, format!("{}{}", indent, entry));
+
+    // The array's length is spelled out in its type
+    let count = entries.len();
+    let old_type = format!("[&dyn Puzzle; {}]", count);
+    let new_type = format!("[&dyn Puzzle; {}]", count + 1);
+    let declaration = lines
+        .iter_mut()
+        .find(|line| line.contains(&old_type))
+        .ok_or_else(|| malformed(&format!("DAYS array of {} days", count)))?;
+    *declaration = declaration.replace(&old_type, &new_type);
+
+    let mut registry = lines.join("\n");
+    registry.push('\n');
+    return Ok(registry);
+}
+
+/// The day number in `line` between `prefix` and `suffix`, if it is shaped like that.
+fn registered_day(line: &str, prefix: &str, suffix: &str) -> Option<u8> {
+    let rest = line.trim().strip_prefix(prefix)?;
+    let (number, _) = rest.split_once(suffix)?;
+    return number.parse().ok();
+}
+
+fn render(template: &str, day: u8) -> String {
+    template.replace("{day}", &day.to_string())
+}
+
+fn read(path: &Path) -> crate::Result<String> {
+    fs::read_to_string(path)
+        .map_err(|e| crate::Error::io(format!("could not read {}", path.display())).with_source(e))
+}
+
+fn write(path: &Path, contents: &str) -> crate::Result<()> {
+    let result = match path.parent() {
+        Some(parent) => fs::create_dir_all(parent).and_then(|_| fs::write(path, contents)),
+        None => fs::write(path, contents),
+    };
+    return result.map_err(|e| {
+        crate::Error::io(format!("could not write {}", path.display())).with_source(e)
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REGISTRY: &str = "\
+use crate::solution::Puzzle;
+
+pub mod day1;
+pub mod day3;
+
+static DAYS: [&dyn Puzzle; 2] = [
+    &day1::Day1,
+    &day3::Day3,
+];
+";
+
+    #[test]
+    fn test_registers_days_in_order() {
+        let registry = register(REGISTRY, 2).unwrap();
+        assert_eq!(
+            registry,
+            "\
+use crate::solution::Puzzle;
+
+pub mod day1;
+pub mod day2;
+pub mod day3;
+
+static DAYS: [&dyn Puzzle; 3] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+];
+"
+        );
+
+        let registry = register(&registry, 4).unwrap();
+        assert!(registry.contains("pub mod day3;\npub mod day4;\n"));
+        assert!(registry.contains("    &day3::Day3,\n    &day4::Day4,\n];"));
+        assert!(registry.contains("[&dyn Puzzle; 4]"));
+    }
+
+    #[test]
+    fn test_refuses_registered_days() {
+        assert!(register(REGISTRY, 3).is_err());
+    }
+
+    #[test]
+    fn test_registers_the_real_registry() {
+        let registry = include_str!("days.rs");
+        let registry = register(registry, 25).unwrap();
+        assert!(registry.contains("pub mod day25;"));
+        assert!(registry.contains("&day25::Day25,"));
+    }
+}
@@ -0,0 +1,186 @@
+use crate::input::InputReader;
+use crate::json;
+use std::any::Any;
+use std::fmt;
+use std::str::FromStr;
+
+/// One half of a day's puzzle.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+impl Part {
+    pub const ALL: [Part; 2] = [Part::One, Part::Two];
+
+    pub fn number(self) -> u8 {
+        match self {
+            Part::One => 1,
+            Part::Two => 2,
+        }
+    }
+}
+
+impl fmt::Display for Part {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.number())
+    }
+}
+
+impl FromStr for Part {
+    type Err = crate::Error;
+
+    fn from_str(s: &str) -> crate::Result<Self> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            _ => Err(crate::Error::parse(format!("no part {}", s))),
+        }
+    }
+}
+
+/// The result of solving one part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Int(i64),
+    UInt(u64),
+    Text(String),
+    /// The part has no solver (yet).
+    Unsolved,
+}
+
+impl Answer {
+    /// Name of the kind of answer, e.g. `int`, or None if unsolved.
+    pub fn type_name(&self) -> Option<&'static str> {
+        match self {
+            Answer::Int(_) => Some("int"),
+            Answer::UInt(_) => Some("uint"),
+            Answer::Text(_) => Some("text"),
+            Answer::Unsolved => None,
+        }
+    }
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Int(v) => write!(f, "{}", v),
+            Answer::UInt(v) => write!(f, "{}", v),
+            Answer::Text(v) => write!(f, "{}", v),
+            Answer::Unsolved => write!(f, "unsolved"),
+        }
+    }
+}
+
+impl From<&Answer> for json::Value {
+    fn from(answer: &Answer) -> Self {
+        match answer {
+            Answer::Int(v) => json::Value::Int(*v),
+            Answer::UInt(v) => json::Value::UInt(*v),
+            Answer::Text(v) => json::Value::from(v.as_str()),
+            Answer::Unsolved => json::Value::Null,
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(v: i32) -> Self {
+        Answer::Int(v as i64)
+    }
+}
+
+impl From<i64> for Answer {
+    fn from(v: i64) -> Self {
+        Answer::Int(v)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(v: u32) -> Self {
+        Answer::UInt(v as u64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(v: u64) -> Self {
+        Answer::UInt(v)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(v: usize) -> Self {
+        Answer::UInt(v as u64)
+    }
+}
+
+impl From<String> for Answer {
+    fn from(v: String) -> Self {
+        Answer::Text(v)
+    }
+}
+
+/// A day's puzzle: parse the input once, then solve either part from it.
+pub trait Solution {
+    type Input;
+
+    /// The day of the advent calendar this solves, starting at 1.
+    fn day(&self) -> u8;
+
+    fn parse(&self, reader: InputReader) -> crate::Result<Self::Input>;
+
+    fn part_one(&self, input: &Self::Input) -> crate::Result<Answer>;
+
+    fn part_two(&self, input: &Self::Input) -> crate::Result<Answer>;
+
+    /// A slow but obviously correct solution of `part` to test the fast one against, or None if
+    /// the day has none.
+    fn reference(&self, _input: &Self::Input, _part: Part) -> Option<crate::Result<Answer>> {
+        None
+    }
+}
+
+/// Type-erased [Solution] so days with different input types can live in one registry.
+pub trait Puzzle: Sync {
+    fn day(&self) -> u8;
+
+    fn parse(&self, reader: InputReader) -> crate::Result<Box<dyn Any>>;
+
+    /// Solves `part` from input previously returned by [Puzzle::parse].
+    fn solve(&self, input: &dyn Any, part: Part) -> crate::Result<Answer>;
+
+    /// Solves `part` with the reference solution, see [Solution::reference].
+    fn reference(&self, input: &dyn Any, part: Part) -> Option<crate::Result<Answer>>;
+}
+
+impl<S> Puzzle for S
+where
+    S: Solution + Sync,
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        Solution::day(self)
+    }
+
+    fn parse(&self, reader: InputReader) -> crate::Result<Box<dyn Any>> {
+        let input = Solution::parse(self, reader)?;
+        return Ok(Box::new(input));
+    }
+
+    fn solve(&self, input: &dyn Any, part: Part) -> crate::Result<Answer> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("input was parsed by a different puzzle");
+        match part {
+            Part::One => self.part_one(input),
+            Part::Two => self.part_two(input),
+        }
+    }
+
+    fn reference(&self, input: &dyn Any, part: Part) -> Option<crate::Result<Answer>> {
+        let input = input
+            .downcast_ref::<S::Input>()
+            .expect("input was parsed by a different puzzle");
+        Solution::reference(self, input, part)
+    }
+}
@@ -0,0 +1,537 @@
+//! Submits answers to the puzzle site, see [Submitter].
+//!
+//! Every response is kept in a [SubmissionLog] so the same answer is never sent twice and the
+//! site's rate limit is waited out locally instead of being hit again.
+
+use crate::config::Config;
+use crate::http::Request;
+use crate::input::Inputs;
+use crate::solution::Part;
+use std::fmt;
+use std::fs;
+use std::io::Write;
+use std::path::PathBuf;
+use std::time::Duration;
+
+/// File name of the [SubmissionLog] within an inputs root.
+pub const SUBMISSIONS_FILE: &str = "submissions";
+
+/// What the site made of a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Correct,
+    TooHigh,
+    TooLow,
+    /// Wrong, without a hint which way.
+    Wrong,
+    /// Submitted too soon after the previous answer, nothing was checked.
+    RateLimited,
+    /// The part was solved before, nothing was checked.
+    AlreadySolved,
+    /// The response was not recognized.
+    Unknown,
+}
+
+impl Outcome {
+    /// Whether the site actually judged the answer.
+    pub fn is_verdict(self) -> bool {
+        !matches!(
+            self,
+            Outcome::RateLimited | Outcome::AlreadySolved | Outcome::Unknown
+        )
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too-high",
+            Outcome::TooLow => "too-low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited => "rate-limited",
+            Outcome::AlreadySolved => "already-solved",
+            Outcome::Unknown => "unknown",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        let all = [
+            Outcome::Correct,
+            Outcome::TooHigh,
+            Outcome::TooLow,
+            Outcome::Wrong,
+            Outcome::RateLimited,
+            Outcome::AlreadySolved,
+            Outcome::Unknown,
+        ];
+        return all.iter().copied().find(|outcome| outcome.name() == name);
+    }
+}
+
+impl fmt::Display for Outcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = match self {
+            Outcome::Correct => "correct",
+            Outcome::TooHigh => "too high",
+            Outcome::TooLow => "too low",
+            Outcome::Wrong => "wrong",
+            Outcome::RateLimited => "submitted too soon",
+            Outcome::AlreadySolved => "already solved",
+            Outcome::Unknown => "not understood",
+        };
+        write!(f, "{}", text)
+    }
+}
+
+/// The site's reply to a submission.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Reply {
+    pub outcome: Outcome,
+    /// How long the site wants us to wait before the next submission.
+    pub wait: Option<Duration>,
+    /// The text of the reply, without markup.
+    pub message: String,
+}
+
+impl Reply {
+    /// Makes sense of the HTML page the site answers a submission with.
+    pub fn parse(html: &str) -> Self {
+        let article = match (html.find("<article"), html.find("</article>")) {
+            (Some(start), Some(end)) if start < end => &html[start..end],
+            _ => html,
+        };
+        let message = strip_tags(article);
+
+        let outcome = if message.contains("That's the right answer") {
+            Outcome::Correct
+        } else if message.contains("That's not the right answer") {
+            if message.contains("your answer is too high") {
+                Outcome::TooHigh
+            } else if message.contains("your answer is too low") {
+                Outcome::TooLow
+            } else {
+                Outcome::Wrong
+            }
+        } else if message.contains("You gave an answer too recently") {
+            Outcome::RateLimited
+        } else if message.contains("Did you already complete it?") {
+            Outcome::AlreadySolved
+        } else {
+            Outcome::Unknown
+        };
+
+        return Reply {
+            outcome,
+            wait: parse_wait(&message),
+            message,
+        };
+    }
+}
+
+/// Collapses `html` to its text, dropping tags and runs of whitespace.
+fn strip_tags(html: &str) -> String {
+    let mut text = String::new();
+    let mut in_tag = false;
+    for c in html.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            c if !in_tag => text.push(c),
+            _ => {}
+        }
+    }
+    let words: Vec<&str> = text.split_whitespace().collect();
+    return words.join(" ");
+}
+
+/// Finds how long to wait in "You have 1m 5s left to wait" or "Please wait one minute before
+/// trying again".
+fn parse_wait(message: &str) -> Option<Duration> {
+    if let Some(end) = message.find(" left to wait") {
+        let start = message[..end].rfind("You have ")? + "You have ".len();
+        let mut seconds: u64 = 0;
+        for amount in message[start..end].split_whitespace() {
+            let (n, unit) = match (
+                amount.strip_suffix('h'),
+                amount.strip_suffix('m'),
+                amount.strip_suffix('s'),
+            ) {
+                (Some(n), _, _) => (n, 3600),
+                (_, Some(n), _) => (n, 60),
+                (_, _, Some(n)) => (n, 1),
+                _ => return None,
+            };
+            let amount = n.parse::<u64>().ok()?.checked_mul(unit)?;
+            seconds = seconds.checked_add(amount)?;
+        }
+        return Some(Duration::from_secs(seconds));
+    }
+
+    let start = message.find("wait ")? + "wait ".len();
+    let mut words = message[start..].split_whitespace();
+    let minutes: u64 = match words.next()? {
+        "one" => 1,
+        n => n.parse().ok()?,
+    };
+    return match words.next()? {
+        "minute" | "minutes" => Some(Duration::from_secs(minutes.checked_mul(60)?)),
+        _ => None,
+    };
+}
+
+/// One recorded submission.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    /// Seconds since the Unix epoch.
+    pub time: u64,
+    pub day: u8,
+    pub part: Part,
+    pub answer: String,
+    pub outcome: Outcome,
+    pub wait: Option<Duration>,
+}
+
+impl fmt::Display for Submission {
+    /// One line of the log: `<time> <day> <part> <outcome> <wait seconds> <answer>`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let wait = self.wait.map_or(0, |wait| wait.as_secs());
+        write!(
+            f,
+            "{} {} {} {} {} {}",
+            self.time,
+            self.day,
+            self.part,
+            self.outcome.name(),
+            wait,
+            self.answer
+        )
+    }
+}
+
+/// Every submission made so far, appended to a file as they happen.
+#[derive(Clone, Debug)]
+pub struct SubmissionLog {
+    path: PathBuf,
+    submissions: Vec<Submission>,
+}
+
+impl SubmissionLog {
+    /// Loads the log at `path`, a missing file is an empty log.
+    pub fn load<P: Into<PathBuf>>(path: P) -> crate::Result<Self> {
+        let path = path.into();
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(_) if !path.exists() => String::new(),
+            Err(e) => {
+                return Err(
+                    crate::Error::io(format!("could not read {}", path.display())).with_source(e),
+                )
+            }
+        };
+
+        let mut submissions = Vec::new();
+        for (i, line) in text.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            match parse_submission(line) {
+                Some(submission) => submissions.push(submission),
+                None => {
+                    return Err(crate::Error::parse(
+                        "expected '<time> <day> <part> <outcome> <wait> <answer>'",
+                    )
+                    .with_line(i + 1, line)
+                    .with_file(path.display().to_string()))
+                }
+            }
+        }
+        return Ok(Self { path, submissions });
+    }
+
+    pub fn submissions(&self) -> &[Submission] {
+        &self.submissions
+    }
+
+    /// Records `submission` in memory and on disk.
+    pub fn append(&mut self, submission: Submission) -> crate::Result<()> {
+        let written = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .and_then(|mut file| writeln!(file, "{}", submission));
+        if let Err(e) = written {
+            return Err(
+                crate::Error::io(format!("could not write {}", self.path.display())).with_source(e),
+            );
+        }
+        self.submissions.push(submission);
+        return Ok(());
+    }
+
+    /// What is already known about submitting `answer` for `day`'s `part`, without asking the
+    /// site.
+    ///
+    /// That is the verdict on the same answer, a correct answer to the part, or for numeric
+    /// answers a bound it falls outside of.
+    pub fn known_outcome(&self, day: u8, part: Part, answer: &str) -> Option<Outcome> {
+        let judged = self
+            .submissions
+            .iter()
+            .filter(|s| s.day == day && s.part == part && s.outcome.is_verdict());
+
+        let value = answer.parse::<i128>().ok();
+        let mut known = None;
+        for submission in judged {
+            if submission.answer == answer {
+                return Some(submission.outcome);
+            }
+            let bound = submission.answer.parse::<i128>().ok();
+            known = match (submission.outcome, value, bound) {
+                (Outcome::Correct, _, _) => Some(Outcome::AlreadySolved),
+                (Outcome::TooHigh, Some(value), Some(bound)) if value >= bound => {
+                    known.or(Some(Outcome::TooHigh))
+                }
+                (Outcome::TooLow, Some(value), Some(bound)) if value <= bound => {
+                    known.or(Some(Outcome::TooLow))
+                }
+                _ => known,
+            };
+        }
+        return known;
+    }
+
+    /// When the site accepts the next submission, in seconds since the Unix epoch.
+    pub fn next_allowed(&self) -> Option<u64> {
+        self.submissions
+            .iter()
+            .filter_map(|s| Some(s.time + s.wait?.as_secs()))
+            .max()
+    }
+}
+
+fn parse_submission(line: &str) -> Option<Submission> {
+    let mut fields = line.splitn(6, ' ');
+    return Some(Submission {
+        time: fields.next()?.parse().ok()?,
+        day: fields.next()?.parse().ok()?,
+        part: fields.next()?.parse().ok()?,
+        outcome: Outcome::from_name(fields.next()?)?,
+        wait: match fields.next()?.parse().ok()? {
+            0 => None,
+            seconds => Some(Duration::from_secs(seconds)),
+        },
+        answer: String::from(fields.next()?),
+    });
+}
+
+/// The result of [Submitter::submit].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Submitted {
+    /// The site judged the answer.
+    Sent(Reply),
+    /// The outcome was already known from the log, the site was not contacted.
+    Known(Outcome),
+}
+
+impl Submitted {
+    pub fn outcome(&self) -> Outcome {
+        match self {
+            Submitted::Sent(reply) => reply.outcome,
+            Submitted::Known(outcome) => *outcome,
+        }
+    }
+}
+
+/// Sends answers to the site, consulting and updating a [SubmissionLog].
+pub struct Submitter {
+    config: Config,
+    log: SubmissionLog,
+    profile: String,
+    year: u16,
+}
+
+impl Submitter {
+    /// Submits as the profile selected by `inputs`, keeping its log next to its inputs.
+    pub fn new(config: Config, inputs: &Inputs) -> crate::Result<Self> {
+        return Ok(Self {
+            config,
+            log: SubmissionLog::load(inputs.submissions_path())?,
+            profile: String::from(inputs.profile()),
+            year: inputs.year(),
+        });
+    }
+
+    pub fn log(&self) -> &SubmissionLog {
+        &self.log
+    }
+
+    /// Submits `answer` for `day`'s `part`, `now` is the time in seconds since the Unix epoch.
+    ///
+    /// Answers whose outcome is already known are not sent, and nothing is sent while the site's
+    /// rate limit is in effect.
+    pub fn submit(
+        &mut self,
+        day: u8,
+        part: Part,
+        answer: &str,
+        now: u64,
+    ) -> crate::Result<Submitted> {
+        if answer.is_empty() || answer.contains(char::is_whitespace) {
+            return Err(crate::Error::validation(format!(
+                "refusing to submit '{}', answers are a single word",
+                answer
+            )));
+        }
+        if let Some(outcome) = self.log.known_outcome(day, part, answer) {
+            return Ok(Submitted::Known(outcome));
+        }
+        if let Some(allowed) = self.log.next_allowed().filter(|&allowed| allowed > now) {
+            return Err(crate::Error::validation(format!(
+                "the site asked to wait before submitting again, try again in {}s",
+                allowed - now
+            )));
+        }
+
+        let url = self
+            .config
+            .base_url()?
+            .join(&format!("{}/day/{}/answer", self.year, day));
+        let level = part.number().to_string();
+        let response = Request::post_form(url.clone(), &[("level", &level), ("answer", answer)])
+            .with_header(
+                "Cookie",
+                &format!("session={}", self.config.session(&self.profile)?),
+            )
+            .send()?;
+        if !response.is_success() {
+            return Err(crate::Error::io(format!(
+                "submitting to {} failed with status {}",
+                url, response.status
+            )));
+        }
+
+        let reply = Reply::parse(&response.body);
+        self.log.append(Submission {
+            time: now,
+            day,
+            part,
+            answer: String::from(answer),
+            outcome: reply.outcome,
+            wait: reply.wait,
+        })?;
+        return Ok(Submitted::Sent(reply));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::http::mock;
+    use std::env;
+
+    const TOO_HIGH: &str = "<main>\n<article><p>That's not the right answer; your answer is too \
+                            high.  If you're stuck, ... Please wait one minute before trying \
+                            again. <a href=\"/2020/day/1\">[Return to Day 1]</a></p></article>\n\
+                            </main>";
+    const TOO_SOON: &str = "<article><p>You gave an answer too recently; you have to wait after \
+                            submitting an answer before trying again.  You have 1m 5s left to \
+                            wait.</p></article>";
+    const RIGHT: &str = "<article><p>That's the right answer!  You are <span>one gold star</span> \
+                         closer to saving your vacation.</p></article>";
+
+    fn scratch(name: &str) -> Inputs {
+        let root = env::temp_dir().join(format!("aoc-submit-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        return Inputs::new(root);
+    }
+
+    fn config(base_url: String) -> Config {
+        Config {
+            base_url,
+            session: Some(String::from("cookie")),
+            ..Config::default()
+        }
+    }
+
+    #[test]
+    fn test_parses_replies() {
+        let reply = Reply::parse(TOO_HIGH);
+        assert_eq!(reply.outcome, Outcome::TooHigh);
+        assert_eq!(reply.wait, Some(Duration::from_secs(60)));
+        assert!(reply.message.starts_with("That's not the right answer;"));
+
+        let reply = Reply::parse(TOO_SOON);
+        assert_eq!(reply.outcome, Outcome::RateLimited);
+        assert_eq!(reply.wait, Some(Duration::from_secs(65)));
+
+        assert_eq!(Reply::parse(RIGHT).outcome, Outcome::Correct);
+        assert_eq!(Reply::parse(RIGHT).wait, None);
+        assert_eq!(Reply::parse("<html>?</html>").outcome, Outcome::Unknown);
+    }
+
+    #[test]
+    fn test_ignores_unreadable_waits() {
+        assert_eq!(
+            parse_wait("You have 1h 2m 3s left to wait."),
+            Some(Duration::from_secs(3723))
+        );
+        assert_eq!(parse_wait("You have 1µs left to wait."), None);
+        assert_eq!(parse_wait("You have µ left to wait."), None);
+        let huge = format!("You have {}h left to wait.", u64::MAX / 2);
+        assert_eq!(parse_wait(&huge), None);
+        let huge = format!("You have {}s {}s left to wait.", u64::MAX, 1);
+        assert_eq!(parse_wait(&huge), None);
+        let huge = format!("Please wait {} minutes before trying again.", u64::MAX);
+        assert_eq!(parse_wait(&huge), None);
+    }
+
+    #[test]
+    fn test_never_resubmits_known_answers() {
+        let (url, server) = mock::serve(vec![
+            mock::response(200, TOO_HIGH),
+            mock::response(200, RIGHT),
+        ]);
+        let inputs = scratch("known");
+        let mut submitter = Submitter::new(config(url), &inputs).unwrap();
+
+        let sent = submitter.submit(1, Part::One, "900", 1000).unwrap();
+        assert_eq!(sent.outcome(), Outcome::TooHigh);
+        // Known without asking, from the answer itself and from the bound it gave
+        assert_eq!(
+            submitter.submit(1, Part::One, "900", 1000).unwrap(),
+            Submitted::Known(Outcome::TooHigh)
+        );
+        assert_eq!(
+            submitter.submit(1, Part::One, "901", 1000).unwrap(),
+            Submitted::Known(Outcome::TooHigh)
+        );
+        // The site asked for a minute
+        let e = submitter.submit(1, Part::One, "500", 1030).unwrap_err();
+        assert!(e.to_string().contains("try again in 30s"));
+
+        let sent = submitter.submit(1, Part::One, "500", 1060).unwrap();
+        assert_eq!(sent.outcome(), Outcome::Correct);
+        assert_eq!(
+            submitter.submit(1, Part::One, "501", 2000).unwrap(),
+            Submitted::Known(Outcome::AlreadySolved)
+        );
+
+        let requests = server.join().unwrap();
+        assert_eq!(requests.len(), 2);
+        assert!(requests[0].starts_with("POST /2020/day/1/answer HTTP/1.1\r\n"));
+        assert!(requests[0].ends_with("\r\n\r\nlevel=1&answer=900"));
+
+        // The log survives a reload
+        let reloaded = SubmissionLog::load(inputs.submissions_path()).unwrap();
+        assert_eq!(reloaded.submissions(), submitter.log().submissions());
+        fs::remove_dir_all(inputs.root()).unwrap();
+    }
+
+    #[test]
+    fn test_rejects_blank_answers() {
+        let inputs = scratch("blank");
+        let mut submitter = Submitter::new(Config::default(), &inputs).unwrap();
+        assert!(submitter.submit(1, Part::One, "", 0).is_err());
+        assert!(submitter.submit(1, Part::One, "1 2", 0).is_err());
+    }
+}
@@ -0,0 +1,77 @@
+//! Known-answer tests of whole days against the worked examples in `inputs/examples`.
+//!
+//! Each day declares its examples with [example_tests](crate::example_tests):
+//!
+//! ```ignore
+//! my::example_tests! {
+//!     Day6;
+//!     test_example_part_one: example 1, part One => 11u32;
+//!     test_example_part_two: example 1, part Two => 6u32;
+//! }
+//! ```
+
+use crate::input::{InputReader, Inputs};
+use crate::solution::{Answer, Part, Solution};
+
+/// The inputs root of this repository, independent of the working directory.
+pub fn inputs() -> Inputs {
+    Inputs::new(concat!(env!("CARGO_MANIFEST_DIR"), "/inputs"))
+}
+
+/// Opens example `index` of `day`, see [Inputs::example_path].
+pub fn example(day: u8, index: usize) -> crate::Result<InputReader> {
+    InputReader::from_path(inputs().example_path(day, Some(index)))
+}
+
+/// Parses example `index` of `solution`'s day and asserts `part` of it solves to `expected`.
+///
+/// # Panics
+///
+/// If the example can not be read, parsed or solved, or the answer is not `expected`.
+pub fn assert_example<S: Solution, A: Into<Answer>>(
+    solution: &S,
+    index: usize,
+    part: Part,
+    expected: A,
+) {
+    let day = solution.day();
+    let fail = |e: crate::Error| -> ! {
+        panic!("day {} example {} part {} failed: {}", day, index, part, e);
+    };
+
+    let input = example(day, index)
+        .and_then(|reader| solution.parse(reader))
+        .unwrap_or_else(|e| fail(e));
+    let answer = match part {
+        Part::One => solution.part_one(&input),
+        Part::Two => solution.part_two(&input),
+    }
+    .unwrap_or_else(|e| fail(e));
+
+    assert_eq!(
+        answer,
+        expected.into(),
+        "day {} example {} part {}",
+        day,
+        index,
+        part
+    );
+}
+
+/// Declares one `#[test]` per listed example, checking it with [assert_example].
+#[macro_export]
+macro_rules! example_tests {
+    ($solution:expr; $($name:ident: example $index:literal, part $part:ident => $expected:expr;)+) => {
+        $(
+            #[test]
+            fn $name() {
+                $crate::testing::assert_example(
+                    &$solution,
+                    $index,
+                    $crate::solution::Part::$part,
+                    $expected,
+                );
+            }
+        )+
+    };
+}